@@ -0,0 +1,357 @@
+// A Typed Alternative to GenError
+
+// The downside of the GenError approach is that the return type no longer
+// communicates precisely what kind of errors the caller can expect. AppError
+// lists them. Its From impls let the ? operator convert io::Error and
+// ParseIntError into an AppError, just as it does for GenError. The crate's
+// other error types become AppError::Other, which keeps the original.
+
+use crate::compile::MissingSemicolonError;
+use crate::error_print::print_error;
+use crate::numbers::LineParseError;
+use crate::prelude::GenError;
+use crate::timeout::TimeoutError;
+use crate::transparent::Transparent;
+use crate::weather::LatLngParseError;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::num::ParseIntError;
+
+/// Everything that can go wrong in this crate's examples.
+///
+/// Each variant displays as the error it holds, and passes that error's
+/// `source()` through; see [`Transparent`].
+#[derive(Debug)]
+pub enum AppError {
+    Io(io::Error),
+    Parse(ParseIntError),
+    /// Anything else. If it came from another error type, `source` holds
+    /// the original error, and `message` is what it displays.
+    Other {
+        message: String,
+        source: Option<GenError>,
+    },
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    /// An `Other` error with just a message.
+    pub fn other<S: Into<String>>(message: S) -> AppError {
+        AppError::Other {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    // An `Other` error that keeps `err` as its source.
+    fn wrap<E: Error + Send + Sync + 'static>(err: E) -> AppError {
+        AppError::Other {
+            message: err.to_string(),
+            source: Some(Box::new(err)),
+        }
+    }
+
+    /// The process exit code for this error: 2 for `Io`, 3 for `Parse`,
+    /// and 1 for `Other`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::Io(_) => 2,
+            AppError::Parse(_) => 3,
+            AppError::Other { .. } => 1,
+        }
+    }
+}
+
+/// Run `f` and return the exit code for its result: 0 on success, or the
+/// error's [`exit_code`](AppError::exit_code) after printing it with
+/// [`print_error`].
+///
+/// ```no_run
+/// use rust_error_result::apperror::{read_numbers, run};
+/// use std::io;
+///
+/// fn main() {
+///     let code = run(|| {
+///         let numbers = read_numbers(&mut io::stdin().lock())?;
+///         println!("{}", numbers.iter().sum::<i64>());
+///         Ok(())
+///     });
+///     std::process::exit(code);
+/// }
+/// ```
+pub fn run<F: FnOnce() -> AppResult<()>>(f: F) -> i32 {
+    match f() {
+        Ok(()) => 0,
+        Err(err) => {
+            print_error(&err);
+            err.exit_code()
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AppError::Io(err) => write!(f, "{}", err),
+            AppError::Parse(err) => write!(f, "{}", err),
+            AppError::Other { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::Io(err) => err.source(),
+            AppError::Parse(err) => err.source(),
+            AppError::Other { source, .. } => source.as_ref().and_then(|err| err.source()),
+        }
+    }
+}
+
+impl Transparent for AppError {
+    fn wrapped(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::Io(err) => Some(err),
+            AppError::Parse(err) => Some(err),
+            AppError::Other { source, .. } => {
+                source.as_deref().map(|err| err as &(dyn Error + 'static))
+            }
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(err: io::Error) -> AppError {
+        AppError::Io(err)
+    }
+}
+
+impl From<ParseIntError> for AppError {
+    fn from(err: ParseIntError) -> AppError {
+        AppError::Parse(err)
+    }
+}
+
+impl From<MissingSemicolonError> for AppError {
+    fn from(err: MissingSemicolonError) -> AppError {
+        AppError::wrap(err)
+    }
+}
+
+impl From<TimeoutError> for AppError {
+    fn from(err: TimeoutError) -> AppError {
+        AppError::wrap(err)
+    }
+}
+
+impl From<LineParseError> for AppError {
+    fn from(err: LineParseError) -> AppError {
+        AppError::wrap(err)
+    }
+}
+
+impl From<LatLngParseError> for AppError {
+    fn from(err: LatLngParseError) -> AppError {
+        AppError::wrap(err)
+    }
+}
+
+/// Turn a `GenError` into an `AppError`, keeping its concrete type if it's
+/// one `AppError` has a variant for.
+///
+/// An `io::Error` becomes `Io`, and a `ParseIntError` becomes `Parse`.
+/// Anything else becomes `Other`, with the box as its source.
+pub fn gen_into_app(err: GenError) -> AppError {
+    let err = match err.downcast::<io::Error>() {
+        Ok(io_err) => return AppError::Io(*io_err),
+        Err(err) => err,
+    };
+    let err = match err.downcast::<ParseIntError>() {
+        Ok(parse_err) => return AppError::Parse(*parse_err),
+        Err(err) => err,
+    };
+    AppError::Other {
+        message: err.to_string(),
+        source: Some(err),
+    }
+}
+
+impl From<GenError> for AppError {
+    fn from(err: GenError) -> AppError {
+        gen_into_app(err)
+    }
+}
+
+/// Read integers from a text file, like [`crate::read_numbers`], but
+/// returning an [`AppError`].
+pub fn read_numbers<R: BufRead>(file: &mut R) -> AppResult<Vec<i64>> {
+    let mut numbers = vec![];
+    for line_result in file.lines() {
+        let line = line_result?; // io::Error -> AppError::Io
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        numbers.push(line.parse()?); // ParseIntError -> AppError::Parse
+    }
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::LatLng;
+    use std::io::Cursor;
+    use std::time::Duration;
+
+    #[test]
+    fn question_mark_converts_both_error_types() {
+        let mut good = Cursor::new("1\n2\n");
+        assert_eq!(read_numbers(&mut good).unwrap(), vec![1, 2]);
+
+        let mut bad = Cursor::new("1\nbleen\n");
+        match read_numbers(&mut bad) {
+            Err(AppError::Parse(err)) => {
+                assert_eq!(err.to_string(), "invalid digit found in string")
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        let mut not_utf8 = Cursor::new(vec![0xff, b'\n']);
+        assert!(matches!(read_numbers(&mut not_utf8), Err(AppError::Io(_))));
+    }
+
+    #[test]
+    fn exit_codes() {
+        let io_err = AppError::from(io::Error::other("disk on fire"));
+        let parse_err = AppError::from("bleen".parse::<i64>().unwrap_err());
+        let other = AppError::other("moon not found");
+        assert_eq!(io_err.exit_code(), 2);
+        assert_eq!(parse_err.exit_code(), 3);
+        assert_eq!(other.exit_code(), 1);
+
+        assert_eq!(run(|| Ok(())), 0);
+        assert_eq!(run(|| Err(AppError::other("moon not found"))), 1);
+        assert_eq!(run(|| Err("bleen".parse::<i64>().unwrap_err().into())), 3);
+    }
+
+    fn original<E: Error + 'static>(err: &AppError) -> &E {
+        match err {
+            AppError::Other {
+                source: Some(source),
+                ..
+            } => source.downcast_ref::<E>().unwrap(),
+            other => panic!("expected an Other error with a source, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn domain_errors_convert_to_other() {
+        fn compile() -> AppResult<()> {
+            Err(MissingSemicolonError::new("src/main.rs", 3))?
+        }
+        fn wait() -> AppResult<()> {
+            Err(TimeoutError {
+                waited: Duration::from_secs(5),
+            })?
+        }
+        fn parse_line() -> AppResult<()> {
+            let source = "bleen".parse::<i64>().unwrap_err();
+            Err(LineParseError { line: 2, source })?
+        }
+        fn locate() -> AppResult<()> {
+            "91,0".parse::<LatLng>()?;
+            Ok(())
+        }
+
+        let err = compile().unwrap_err();
+        assert_eq!(err.to_string(), "missing semicolon at src/main.rs:3");
+        assert_eq!(original::<MissingSemicolonError>(&err).line(), 3);
+        assert_eq!(err.exit_code(), 1);
+
+        let err = wait().unwrap_err();
+        assert_eq!(
+            original::<TimeoutError>(&err).waited,
+            Duration::from_secs(5)
+        );
+
+        let err = parse_line().unwrap_err();
+        assert_eq!(original::<LineParseError>(&err).line, 2);
+        assert!(err.source().unwrap().is::<ParseIntError>());
+
+        let err = locate().unwrap_err();
+        assert_eq!(err.to_string(), "no such location: (91, 0)");
+        assert!(original::<LatLngParseError>(&err).source().is_none());
+    }
+
+    #[test]
+    fn wrapped_error_stays_reachable() {
+        let err = AppError::from(MissingSemicolonError::new("src/main.rs", 3));
+        assert!(err.source().is_none());
+        let original = err.wrapped().unwrap();
+        assert_eq!(
+            original
+                .downcast_ref::<MissingSemicolonError>()
+                .unwrap()
+                .line(),
+            3
+        );
+        assert!(crate::downcast_root::<MissingSemicolonError>(&err).is_some());
+
+        let err = AppError::from(io::Error::new(io::ErrorKind::NotFound, "no tides"));
+        assert!(crate::chain_contains::<io::Error>(&err));
+        let io_err = crate::downcast_root::<io::Error>(&err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+
+        let err = AppError::from(TimeoutError {
+            waited: Duration::from_secs(5),
+        });
+        assert_eq!(crate::categorize(&err), crate::ErrorCategory::Timeout);
+
+        assert!(AppError::other("moon not found").wrapped().is_none());
+    }
+
+    #[test]
+    fn chain_shows_message_once() {
+        let err = AppError::from(MissingSemicolonError::new("a.rs", 3));
+        let mut out = Vec::new();
+        crate::write_error(&mut out, &err).unwrap();
+        assert_eq!(out, b"error: missing semicolon at a.rs:3\n");
+    }
+
+    #[test]
+    fn gen_error_keeps_concrete_type() {
+        let io_err = GenError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        match gen_into_app(io_err) {
+            AppError::Io(err) => assert_eq!(err.kind(), io::ErrorKind::TimedOut),
+            other => panic!("expected an Io error, got {:?}", other),
+        }
+
+        let parse_err = GenError::from("bleen".parse::<i64>().unwrap_err());
+        assert!(matches!(AppError::from(parse_err), AppError::Parse(_)));
+
+        fn call_library() -> AppResult<()> {
+            let failed: crate::prelude::GenResult<()> = Err(crate::err!("moon not found"));
+            Ok(failed?)
+        }
+        let err = call_library().unwrap_err();
+        assert_eq!(err.to_string(), "moon not found");
+        assert_eq!(original::<crate::MsgError>(&err).0, "moon not found");
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[test]
+    fn gen_error_fallback_keeps_original() {
+        let err = gen_into_app(GenError::from(MissingSemicolonError::new("src/main.rs", 3)));
+        assert_eq!(err.to_string(), "missing semicolon at src/main.rs:3");
+        let original = err
+            .wrapped()
+            .unwrap()
+            .downcast_ref::<MissingSemicolonError>();
+        assert_eq!(original.unwrap().line(), 3);
+        assert!(crate::chain_contains::<MissingSemicolonError>(&err));
+    }
+}
@@ -0,0 +1,109 @@
+// Caching Results
+
+// A successful lookup can be remembered, but an error is often worth trying
+// again later: the network comes back, the file shows up. ResultCache keeps
+// only the Ok values, so a key that failed is computed again next time.
+
+use crate::prelude::GenResult;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Memoizes successful results of a fallible computation.
+///
+/// ```
+/// use rust_error_result::{get_weather, LatLng, ResultCache};
+///
+/// let mut cache = ResultCache::new();
+/// let la = LatLng { lat: 34.05, lng: -118.24 };
+/// let key = format!("{},{}", la.lat, la.lng);
+/// let report = *cache.get_or_try_insert(key, || Ok(get_weather(la)?)).unwrap();
+/// assert_eq!(report, get_weather(la).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResultCache<K, V> {
+    values: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V> ResultCache<K, V> {
+    pub fn new() -> ResultCache<K, V> {
+        ResultCache {
+            values: HashMap::new(),
+        }
+    }
+
+    /// The cached value for `key`, if there is one; otherwise call `f` and
+    /// cache its value if it succeeds.
+    ///
+    /// Errors aren't cached, so `f` is called again on the next lookup of a
+    /// key that failed.
+    pub fn get_or_try_insert<F: FnOnce() -> GenResult<V>>(
+        &mut self,
+        key: K,
+        f: F,
+    ) -> GenResult<&V> {
+        match self.values.entry(key) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => Ok(entry.insert(f()?)),
+        }
+    }
+
+    /// The cached value for `key`, without computing anything.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<K: Eq + Hash, V> Default for ResultCache<K, V> {
+    fn default() -> ResultCache<K, V> {
+        ResultCache::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn failure_is_retried_then_cached() {
+        let mut cache = ResultCache::new();
+        let calls = Cell::new(0);
+        let lookup = || {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 {
+                Err(crate::err!("network outage"))
+            } else {
+                Ok(72)
+            }
+        };
+
+        let err = cache.get_or_try_insert("la", lookup).unwrap_err();
+        assert_eq!(err.to_string(), "network outage");
+        assert!(cache.get(&"la").is_none());
+
+        assert_eq!(*cache.get_or_try_insert("la", lookup).unwrap(), 72);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(cache.get(&"la"), Some(&72));
+    }
+
+    #[test]
+    fn cached_key_skips_the_closure() {
+        let mut cache = ResultCache::new();
+        assert_eq!(
+            *cache.get_or_try_insert(1, || Ok("sunny")).unwrap(),
+            "sunny"
+        );
+        let again = cache.get_or_try_insert(1, || panic!("called for a cached key"));
+        assert_eq!(*again.unwrap(), "sunny");
+        assert_eq!(cache.len(), 1);
+    }
+}
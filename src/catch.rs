@@ -0,0 +1,64 @@
+// Turning Panics into Errors
+
+// Some code panics where it should have returned an error. catch() runs it
+// and turns a panic into an ordinary GenError, so the caller can handle it
+// like any other.
+
+use crate::prelude::GenResult;
+use crate::string_error::MsgError;
+use std::any::Any;
+use std::panic::{self, UnwindSafe};
+
+/// Run `f`, returning its result, or an error if it panics.
+///
+/// The error's message is the panic message when the panic payload is a
+/// string, as it is for `panic!` with a message.
+///
+/// The panic hook still runs, so by default the panic is still reported on
+/// stderr.
+///
+/// ```
+/// use rust_error_result::catch::catch;
+///
+/// assert_eq!(catch(|| 2 + 2).unwrap(), 4);
+/// ```
+pub fn catch<T, F: FnOnce() -> T + UnwindSafe>(f: F) -> GenResult<T> {
+    panic::catch_unwind(f).map_err(|payload| MsgError::from(panic_message(&*payload)).into())
+}
+
+/// The message from a panic payload, if it has one.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panicked with a non-string payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_return() {
+        assert_eq!(catch(|| "fine").unwrap(), "fine");
+    }
+
+    #[test]
+    fn panic_message_becomes_error() {
+        let err = catch(|| -> () { panic!("boom") }).unwrap_err();
+        assert_eq!(err.to_string(), "boom");
+
+        let code = 7;
+        let err = catch(|| -> () { panic!("boom {}", code) }).unwrap_err();
+        assert_eq!(err.to_string(), "boom 7");
+    }
+
+    #[test]
+    fn non_string_payload() {
+        let err = catch(|| -> () { panic::panic_any(42) }).unwrap_err();
+        assert_eq!(err.to_string(), "panicked with a non-string payload");
+    }
+}
@@ -0,0 +1,89 @@
+// Error Categories
+
+// For metrics, the exact error type matters less than what broad kind of
+// problem it was. categorize() looks through an error's chain for an error it
+// recognizes, and sorts it into an ErrorCategory.
+
+use crate::chain::static_chain;
+use crate::timeout::TimeoutError;
+use std::error::Error;
+use std::io;
+use std::num::ParseIntError;
+
+/// Broad kinds of errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    NotFound,
+    PermissionDenied,
+    Timeout,
+    InvalidInput,
+    Other,
+}
+
+/// The category of the first error in `err`'s chain that has one.
+///
+/// `io::Error`s are sorted by kind, a `ParseIntError` is `InvalidInput`,
+/// and a [`TimeoutError`] is `Timeout`. If nothing in the chain is
+/// recognized, the category is `Other`.
+///
+/// Only errors with no borrowed data can be downcast, so `err` must be
+/// `'static`.
+pub fn categorize(err: &(dyn Error + 'static)) -> ErrorCategory {
+    static_chain(err)
+        .map(category_of)
+        .find(|&category| category != ErrorCategory::Other)
+        .unwrap_or(ErrorCategory::Other)
+}
+
+// The category of just this error, ignoring its sources.
+fn category_of(err: &(dyn Error + 'static)) -> ErrorCategory {
+    if let Some(io_err) = err.downcast_ref::<io::Error>() {
+        match io_err.kind() {
+            io::ErrorKind::NotFound => ErrorCategory::NotFound,
+            io::ErrorKind::PermissionDenied => ErrorCategory::PermissionDenied,
+            io::ErrorKind::TimedOut => ErrorCategory::Timeout,
+            io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => ErrorCategory::InvalidInput,
+            _ => ErrorCategory::Other,
+        }
+    } else if err.is::<ParseIntError>() {
+        ErrorCategory::InvalidInput
+    } else if err.is::<TimeoutError>() {
+        ErrorCategory::Timeout
+    } else {
+        ErrorCategory::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ContextError;
+    use crate::string_error::MsgError;
+    use std::time::Duration;
+
+    #[test]
+    fn categories() {
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "moon not found");
+        assert_eq!(categorize(&not_found), ErrorCategory::NotFound);
+
+        let parse_err = "bleen".parse::<u64>().unwrap_err();
+        assert_eq!(categorize(&parse_err), ErrorCategory::InvalidInput);
+
+        let timeout = TimeoutError {
+            waited: Duration::from_secs(30),
+        };
+        assert_eq!(categorize(&timeout), ErrorCategory::Timeout);
+
+        let unknown = MsgError::from("boat was repossessed");
+        assert_eq!(categorize(&unknown), ErrorCategory::Other);
+    }
+
+    #[test]
+    fn looks_through_the_chain() {
+        let wrapped = ContextError {
+            context: "reading tide table".to_string(),
+            source: Box::new(io::Error::new(io::ErrorKind::PermissionDenied, "denied")),
+        };
+        assert_eq!(categorize(&wrapped), ErrorCategory::PermissionDenied);
+    }
+}
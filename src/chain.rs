@@ -0,0 +1,169 @@
+// Walking the Error Chain
+
+// err.source() returns the underlying error, if any, that triggered err. That
+// error may have a source of its own, and so on down to the root cause, whose
+// source() returns None. ErrorChain iterates over all of them, starting with
+// err itself.
+
+use crate::core_error::Error;
+
+/// Most chains are a handful of errors long. A source() implementation that
+/// (directly or indirectly) returns itself would loop forever, so iteration
+/// stops after this many errors.
+pub const MAX_DEPTH: usize = 128;
+
+/// Iterator over an error and each of its successive sources.
+///
+/// Created by [`chain`].
+#[derive(Clone)]
+pub struct ErrorChain<'a> {
+    next: Option<&'a dyn Error>,
+    depth: usize,
+}
+
+/// Iterate over `err` followed by each error returned by `source()`.
+///
+/// ```
+/// use rust_error_result::chain;
+/// use std::io;
+///
+/// let err = io::Error::other("moon not found");
+/// assert_eq!(chain(&err).count(), 1);
+/// ```
+pub fn chain(err: &dyn Error) -> ErrorChain<'_> {
+    ErrorChain {
+        next: Some(err),
+        depth: 0,
+    }
+}
+
+/// The deepest cause of `err`: the last error in its chain.
+///
+/// If `err` has no source, that's `err` itself.
+pub fn root_cause(err: &dyn Error) -> &dyn Error {
+    chain(err).last().unwrap_or(err)
+}
+
+/// The number of errors in `err`'s chain, counting `err` itself.
+pub fn chain_len(err: &dyn Error) -> usize {
+    chain(err).count()
+}
+
+/// True if there is an `E` anywhere in `err`'s chain, `err` included.
+///
+/// Only errors with no borrowed data can be downcast, so `err` must be
+/// `'static`.
+#[cfg(feature = "std")]
+pub fn chain_contains<E: Error + 'static>(err: &(dyn Error + 'static)) -> bool {
+    static_chain(err).any(|e| e.is::<E>())
+}
+
+// Like chain(), but keeps the 'static bound that downcasting needs. That
+// holds for every source, so only the first error has to have it. It also
+// steps into the error wrapped by each Transparent wrapper, which source()
+// skips over.
+#[cfg(feature = "std")]
+pub(crate) fn static_chain<'a>(
+    err: &'a (dyn Error + 'static),
+) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+    std::iter::successors(Some(err), |&e| {
+        crate::transparent::wrapped(e).or_else(|| e.source())
+    })
+    .take(MAX_DEPTH)
+}
+
+impl<'a> Iterator for ErrorChain<'a> {
+    type Item = &'a dyn Error;
+
+    fn next(&mut self) -> Option<&'a dyn Error> {
+        if self.depth == MAX_DEPTH {
+            return None;
+        }
+        let err = self.next?;
+        self.next = err.source();
+        self.depth += 1;
+        Some(err)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+pub(crate) mod tests {
+    use super::*;
+    use std::fmt;
+    use std::io;
+    use std::num::ParseIntError;
+
+    #[derive(Debug)]
+    struct Wrapped(&'static str, Box<dyn Error>);
+
+    impl fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl Error for Wrapped {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&*self.1)
+        }
+    }
+
+    /// An error that is its own source, for testing depth limits.
+    #[derive(Debug)]
+    pub(crate) struct Cyclic;
+
+    impl fmt::Display for Cyclic {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "caused by itself")
+        }
+    }
+
+    impl Error for Cyclic {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(self)
+        }
+    }
+
+    #[test]
+    fn single_error_yields_itself() {
+        let err = io::Error::other("moon not found");
+        let messages: Vec<String> = chain(&err).map(|e| e.to_string()).collect();
+        assert_eq!(messages, vec!["moon not found"]);
+    }
+
+    #[test]
+    fn root_cause_is_deepest_error() {
+        let root = io::Error::other("network outage");
+        let middle = Wrapped(
+            "failed to transfer $300 to United Yacht Supply",
+            Box::new(root),
+        );
+        let top = Wrapped("boat was repossessed", Box::new(middle));
+        assert_eq!(root_cause(&top).to_string(), "network outage");
+
+        let alone = io::Error::other("moon not found");
+        assert_eq!(root_cause(&alone).to_string(), "moon not found");
+    }
+
+    #[test]
+    fn chain_len_and_contains() {
+        let root = "bleen".parse::<u64>().unwrap_err();
+        let middle = Wrapped("bad tide table", Box::new(root));
+        let top = Wrapped("moon not found", Box::new(middle));
+        assert_eq!(chain_len(&top), 3);
+        assert!(chain_contains::<ParseIntError>(&top));
+        assert!(chain_contains::<Wrapped>(&top));
+        assert!(!chain_contains::<io::Error>(&top));
+
+        let alone = io::Error::other("moon not found");
+        assert_eq!(chain_len(&alone), 1);
+        assert!(chain_contains::<io::Error>(&alone));
+        assert!(!chain_contains::<ParseIntError>(&alone));
+    }
+
+    #[test]
+    fn cyclic_chain_stops_at_max_depth() {
+        assert_eq!(chain(&Cyclic).count(), MAX_DEPTH);
+        assert_eq!(root_cause(&Cyclic).to_string(), "caused by itself");
+    }
+}
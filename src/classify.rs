@@ -0,0 +1,66 @@
+// Transient vs Fatal Errors
+
+// Some errors are worth another try: a timeout or a dropped connection may
+// well go away on its own. Others won't: retrying won't make a missing file
+// appear. is_transient() tells them apart by io::ErrorKind.
+
+use crate::downcast::downcast_root;
+use crate::prelude::GenError;
+use std::io;
+
+/// True if the operation that failed with `err` might succeed if tried
+/// again.
+pub fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::BrokenPipe
+    )
+}
+
+/// Errors that know whether they are worth retrying.
+pub trait Transient {
+    fn is_transient(&self) -> bool;
+}
+
+impl Transient for io::Error {
+    fn is_transient(&self) -> bool {
+        is_transient(self)
+    }
+}
+
+/// A `GenError` is transient if its root cause is a transient `io::Error`.
+impl Transient for GenError {
+    fn is_transient(&self) -> bool {
+        downcast_root::<io::Error>(&**self).is_some_and(is_transient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_error_kinds() {
+        let table = [
+            (io::ErrorKind::TimedOut, true),
+            (io::ErrorKind::Interrupted, true),
+            (io::ErrorKind::WouldBlock, true),
+            (io::ErrorKind::ConnectionReset, true),
+            (io::ErrorKind::NotFound, false),
+            (io::ErrorKind::PermissionDenied, false),
+            (io::ErrorKind::InvalidInput, false),
+            (io::ErrorKind::InvalidData, false),
+            (io::ErrorKind::Other, false),
+        ];
+        for &(kind, expected) in table.iter() {
+            let err = io::Error::new(kind, "test");
+            assert_eq!(is_transient(&err), expected, "{:?}", kind);
+        }
+    }
+}
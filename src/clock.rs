@@ -0,0 +1,126 @@
+// Clocks
+
+// Helpers that wait or measure time, like retry_with_backoff() and
+// ErrorThrottle, are hard to test against the real clock: the tests are slow,
+// and their timing is never quite the same twice. These helpers take a Clock
+// instead. SystemClock is the real one; FakeClock only moves when told to.
+
+use std::cell::{Cell, RefCell};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// A source of the current time, and a way to wait.
+pub trait Clock {
+    /// The current time, for measuring intervals.
+    fn now(&self) -> Instant;
+
+    /// The current time of day, for comparing against file times.
+    fn system_now(&self) -> SystemTime;
+
+    /// Wait for `dur`.
+    fn sleep(&self, dur: Duration);
+}
+
+/// The real clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, dur: Duration) {
+        thread::sleep(dur);
+    }
+}
+
+/// A clock for tests. Time stands still except when
+/// [`advance`](FakeClock::advance) or [`sleep`](Clock::sleep) moves it
+/// forward, and each sleep is recorded instead of actually waiting.
+///
+/// ```
+/// use rust_error_result::{Clock, FakeClock};
+/// use std::time::Duration;
+///
+/// let clock = FakeClock::new();
+/// let start = clock.now();
+/// clock.sleep(Duration::from_secs(5));
+/// assert_eq!(clock.now() - start, Duration::from_secs(5));
+/// assert_eq!(clock.sleeps(), vec![Duration::from_secs(5)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    start: Instant,
+    system_start: SystemTime,
+    elapsed: Cell<Duration>,
+    sleeps: RefCell<Vec<Duration>>,
+}
+
+impl FakeClock {
+    /// A fake clock that starts at the real current time.
+    pub fn new() -> FakeClock {
+        FakeClock {
+            start: Instant::now(),
+            system_start: SystemTime::now(),
+            elapsed: Cell::new(Duration::from_secs(0)),
+            sleeps: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Move the clock forward by `dur`, without recording a sleep.
+    pub fn advance(&self, dur: Duration) {
+        self.elapsed.set(self.elapsed.get() + dur);
+    }
+
+    /// Every duration passed to [`sleep`](Clock::sleep), in order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.borrow().clone()
+    }
+}
+
+impl Default for FakeClock {
+    fn default() -> FakeClock {
+        FakeClock::new()
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed.get()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        self.system_start + self.elapsed.get()
+    }
+
+    fn sleep(&self, dur: Duration) {
+        self.sleeps.borrow_mut().push(dur);
+        self.advance(dur);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_clock_moves_only_when_told() {
+        let clock = FakeClock::new();
+        let (start, system_start) = (clock.now(), clock.system_now());
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_secs(60));
+        clock.sleep(Duration::from_secs(30));
+        assert_eq!(clock.now() - start, Duration::from_secs(90));
+        assert_eq!(
+            clock.system_now().duration_since(system_start).unwrap(),
+            Duration::from_secs(90)
+        );
+        assert_eq!(clock.sleeps(), vec![Duration::from_secs(30)]);
+    }
+}
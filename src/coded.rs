@@ -0,0 +1,154 @@
+// Exit Codes
+
+// If we propagate an error long enough, eventually it reaches main(). A CLI
+// should then print it and exit with a nonzero exit code. CodedError carries
+// the exit code along with the error, so main() knows which one to use.
+
+use crate::error_print::print_error;
+use crate::prelude::GenError;
+use crate::transparent::Transparent;
+use std::error::Error;
+use std::fmt;
+use std::process;
+
+/// The exit code used for errors that weren't given one.
+pub const DEFAULT_CODE: i32 = 1;
+
+/// An error tagged with a process exit code.
+///
+/// Displays as the wrapped error does, and passes its `source()` through;
+/// see [`Transparent`].
+#[derive(Debug)]
+pub struct CodedError {
+    code: i32,
+    source: GenError,
+}
+
+impl CodedError {
+    pub fn new<E: Into<GenError>>(code: i32, err: E) -> CodedError {
+        CodedError {
+            code,
+            source: err.into(),
+        }
+    }
+
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    pub fn into_inner(self) -> GenError {
+        self.source
+    }
+}
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.source, f)
+    }
+}
+
+impl Error for CodedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.source()
+    }
+}
+
+impl Transparent for CodedError {
+    fn wrapped(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// An untagged error gets [`DEFAULT_CODE`], so `?` works in functions that
+/// return a `CodedError`.
+impl From<GenError> for CodedError {
+    fn from(err: GenError) -> CodedError {
+        CodedError::new(DEFAULT_CODE, err)
+    }
+}
+
+pub trait CodedResultExt<T> {
+    /// Tag the error, if any, with the exit code `code`.
+    fn code(self, code: i32) -> Result<T, CodedError>;
+}
+
+impl<T, E: Into<GenError>> CodedResultExt<T> for Result<T, E> {
+    fn code(self, code: i32) -> Result<T, CodedError> {
+        self.map_err(|err| CodedError::new(code, err))
+    }
+}
+
+/// Run `f`, then exit the process.
+///
+/// If `f` succeeds, the exit code is 0. Otherwise the error is printed with
+/// [`print_error`], and the exit code is the error's code. A code of 0
+/// would look like success, so [`DEFAULT_CODE`] is used instead.
+///
+/// ```no_run
+/// use rust_error_result::coded::{run_and_exit, CodedResultExt};
+/// use rust_error_result::{get_weather, LatLng};
+///
+/// fn main() {
+///     run_and_exit(|| {
+///         let hometown = LatLng { lat: 34.05, lng: -118.24 };
+///         get_weather(hometown).code(2)?;
+///         Ok(())
+///     })
+/// }
+/// ```
+pub fn run_and_exit<F: FnOnce() -> Result<(), CodedError>>(f: F) -> ! {
+    match f() {
+        Ok(()) => process::exit(0),
+        Err(err) => {
+            print_error(&err);
+            match err.code() {
+                0 => process::exit(DEFAULT_CODE),
+                code => process::exit(code),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn code_round_trips() {
+        let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+        let err = failed.code(3).unwrap_err();
+        assert_eq!(err.code(), 3);
+        assert_eq!(err.to_string(), "moon not found");
+
+        let ok: io::Result<i32> = Ok(7);
+        assert_eq!(ok.code(3).unwrap(), 7);
+    }
+
+    #[test]
+    fn untagged_errors_get_default_code() {
+        fn calculate_tides() -> Result<(), CodedError> {
+            Err(crate::err!("moon not found"))?;
+            Ok(())
+        }
+        assert_eq!(calculate_tides().unwrap_err().code(), DEFAULT_CODE);
+    }
+
+    #[test]
+    fn code_keeps_error_reachable() {
+        let missing: io::Result<()> = Err(io::ErrorKind::NotFound.into());
+        let err = missing.code(2).unwrap_err();
+        assert!(crate::chain_contains::<io::Error>(&err));
+        let io_err = crate::downcast_root::<io::Error>(&err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn chain_shows_message_once() {
+        let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+        let err = failed.code(3).unwrap_err();
+        let mut out = Vec::new();
+        crate::write_error(&mut out, &err).unwrap();
+        assert_eq!(out, b"error: moon not found\n");
+    }
+}
@@ -0,0 +1,161 @@
+// Collecting Errors
+
+// Since Result is a data type like any other, it's easy to model partial
+// success. Rather than stopping at the first bad line of a file, we can keep
+// going and report every error at the end.
+
+use crate::chain::chain;
+use crate::prelude::{GenError, GenResult};
+use std::error::Error;
+use std::fmt;
+
+/// A list of errors gathered while carrying on past each one.
+#[derive(Debug, Default)]
+pub struct ErrorCollection {
+    errors: Vec<GenError>,
+}
+
+impl ErrorCollection {
+    pub fn new() -> ErrorCollection {
+        ErrorCollection::default()
+    }
+
+    pub fn push(&mut self, err: GenError) {
+        self.errors.push(err);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// `Ok(value)` if no errors were collected; otherwise a
+    /// [`CollectedErrors`] holding all of them.
+    pub fn into_result<T>(self, value: T) -> GenResult<T> {
+        if self.errors.is_empty() {
+            Ok(value)
+        } else {
+            Err(self.into())
+        }
+    }
+}
+
+/// Combine the collected errors into one [`CollectedErrors`].
+///
+/// Converting an empty collection is a logic error: there's nothing to
+/// report. It still gives an error, one whose message is "no errors".
+impl From<ErrorCollection> for GenError {
+    fn from(collection: ErrorCollection) -> GenError {
+        Box::new(CollectedErrors {
+            errors: collection.errors,
+        })
+    }
+}
+
+/// Several errors reported as one.
+///
+/// Displays every error's message, with its causes, one per line. A cause
+/// is left out if the message before it already ends with it, as a
+/// [`LineParseError`](crate::numbers::LineParseError)'s does. `source()` is
+/// the first error collected.
+#[derive(Debug)]
+pub struct CollectedErrors {
+    errors: Vec<GenError>,
+}
+
+impl CollectedErrors {
+    pub fn errors(&self) -> &[GenError] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for CollectedErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.errors.len() {
+            0 => return write!(f, "no errors"),
+            1 => write!(f, "1 error:")?,
+            n => write!(f, "{} errors:", n)?,
+        }
+        for err in &self.errors {
+            let mut last = err.to_string();
+            write!(f, "\n  {}", last)?;
+            for cause in chain(&**err).skip(1) {
+                let message = cause.to_string();
+                if !last.ends_with(&message) {
+                    write!(f, ": {}", message)?;
+                }
+                last = message;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for CollectedErrors {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.errors
+            .first()
+            .map(|err| &**err as &(dyn Error + 'static))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numbers::{read_numbers_all, LineParseError};
+    use std::io::Cursor;
+
+    #[test]
+    fn empty_collection_is_ok() {
+        assert_eq!(ErrorCollection::new().into_result(5).unwrap(), 5);
+    }
+
+    #[test]
+    fn converts_into_gen_error() {
+        let mut errors = ErrorCollection::new();
+        errors.push(crate::err!("moon not found"));
+        errors.push(crate::err!("tide table missing"));
+
+        let err = GenError::from(errors);
+        assert_eq!(
+            err.to_string(),
+            "2 errors:\n  moon not found\n  tide table missing"
+        );
+        assert_eq!(err.source().unwrap().to_string(), "moon not found");
+
+        let collected = err.downcast_ref::<CollectedErrors>().unwrap();
+        let messages: Vec<String> = collected.errors().iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages, vec!["moon not found", "tide table missing"]);
+    }
+
+    #[test]
+    fn empty_collection_converts_to_placeholder() {
+        let err = GenError::from(ErrorCollection::new());
+        assert_eq!(err.to_string(), "no errors");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn reports_every_bad_line() {
+        let mut file = Cursor::new("1\nbleen\n3\n\n99999999999999999999\n");
+        let err = read_numbers_all(&mut file).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 errors:\n  \
+             parse error on line 2: invalid digit found in string\n  \
+             parse error on line 5: number too large to fit in target type"
+        );
+
+        let collected = err.downcast_ref::<CollectedErrors>().unwrap();
+        let lines: Vec<usize> = collected
+            .errors()
+            .iter()
+            .map(|e| e.downcast_ref::<LineParseError>().unwrap().line)
+            .collect();
+        assert_eq!(lines, [2, 5]);
+        assert!(err.source().unwrap().is::<LineParseError>());
+    }
+}
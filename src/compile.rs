@@ -0,0 +1,183 @@
+// A Fixable Error
+
+// Sometimes the caller can do something about one particular kind of error.
+// A compiler that reports a MissingSemicolonError could have the semicolon
+// inserted for it, and then try again.
+
+use crate::prelude::GenResult;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A source line that should have ended with a semicolon, but didn't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingSemicolonError {
+    file: PathBuf,
+    line: usize,
+}
+
+impl MissingSemicolonError {
+    pub fn new<P: Into<PathBuf>>(file: P, line: usize) -> MissingSemicolonError {
+        MissingSemicolonError {
+            file: file.into(),
+            line,
+        }
+    }
+
+    /// The file the semicolon is missing from.
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    /// The line number, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for MissingSemicolonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "missing semicolon at {}:{}",
+            self.file.display(),
+            self.line
+        )
+    }
+}
+
+impl Error for MissingSemicolonError {}
+
+/// "Compile" the lines of `file`.
+///
+/// This is only a stub: every line that isn't blank must end with a
+/// semicolon. The first line that doesn't is reported as a
+/// [`MissingSemicolonError`].
+pub fn compile_project<S: AsRef<str>>(file: &Path, source: &[S]) -> GenResult<()> {
+    for (i, line) in source.iter().enumerate() {
+        let line = line.as_ref().trim_end();
+        if !line.is_empty() && !line.ends_with(';') {
+            return Err(MissingSemicolonError::new(file, i + 1).into());
+        }
+    }
+    Ok(())
+}
+
+/// A one-file project for [`compile_project`] to build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    file: PathBuf,
+    lines: Vec<String>,
+}
+
+impl Project {
+    pub fn new<P: Into<PathBuf>>(file: P, lines: Vec<String>) -> Project {
+        Project {
+            file: file.into(),
+            lines,
+        }
+    }
+
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Compile the project with [`compile_project`].
+    pub fn compile(&self) -> GenResult<()> {
+        compile_project(&self.file, &self.lines)
+    }
+
+    /// Add a semicolon to the end of `line` of `file`, counting from 1.
+    ///
+    /// Does nothing if `file` isn't this project's file. Panics if there's
+    /// no such line.
+    pub fn insert_semicolon(&mut self, file: &Path, line: usize) {
+        if file == self.file {
+            self.lines[line - 1].push(';');
+        }
+    }
+}
+
+/// Compile `project`, fixing each missing semicolon and trying again until
+/// it builds. Any other error is returned.
+pub fn build_until_success(project: &mut Project) -> GenResult<()> {
+    loop {
+        match project.compile() {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                if let Some(mse) = err.downcast_ref::<MissingSemicolonError>() {
+                    project.insert_semicolon(mse.file(), mse.line());
+                    continue; // try again!
+                }
+                return Err(err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_on_missing_semicolon() {
+        let file = Path::new("src/main.rs");
+        let mut source = vec![
+            "let x = 1".to_string(),
+            "let y = 2;".to_string(),
+            "let z = x + y".to_string(),
+        ];
+
+        let mut fixes = vec![];
+        loop {
+            match compile_project(file, &source) {
+                Ok(()) => break,
+                Err(err) => {
+                    if let Some(mse) = err.downcast_ref::<MissingSemicolonError>() {
+                        assert_eq!(mse.file(), file);
+                        fixes.push(mse.line());
+                        source[mse.line() - 1].push(';');
+                        continue; // try again!
+                    }
+                    panic!("unexpected error: {}", err);
+                }
+            }
+        }
+
+        assert_eq!(fixes, vec![1, 3]);
+    }
+
+    #[test]
+    fn display_names_file_and_line() {
+        let err = MissingSemicolonError::new("src/lib.rs", 12);
+        assert_eq!(err.to_string(), "missing semicolon at src/lib.rs:12");
+    }
+
+    #[test]
+    fn build_until_success_fixes_every_line() {
+        let mut project = Project::new(
+            "src/main.rs",
+            vec![
+                "let x = 1".to_string(),
+                "let y = 2;".to_string(),
+                "".to_string(),
+                "let z = x + y".to_string(),
+            ],
+        );
+        assert!(project.compile().is_err());
+
+        build_until_success(&mut project).unwrap();
+
+        assert_eq!(
+            project.lines(),
+            ["let x = 1;", "let y = 2;", "", "let z = x + y;"]
+        );
+        let semicolons: usize = project.lines().iter().map(|l| l.matches(';').count()).sum();
+        assert_eq!(semicolons, 3);
+        assert!(project.compile().is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+// Config Errors
+
+// When a config file doesn't parse, the most useful thing we can tell the user
+// is exactly where. ConfigError carries the line and column along with what
+// was wrong there.
+
+use std::error::Error;
+use std::fmt;
+
+/// A problem at a specific place in a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// The line number, starting from 1.
+    pub line: usize,
+    /// The column, in characters, starting from 1.
+    pub col: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "config error at {}:{}: {}",
+            self.line, self.col, self.message
+        )
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Parse `key = value` lines.
+///
+/// Whitespace around keys and values is trimmed. Blank lines, and lines
+/// starting with `#`, are skipped. A line with no `=` is reported at the
+/// column just past its end, where the `=` was expected; a line with
+/// nothing before the `=` is reported at the `=`.
+///
+/// ```
+/// use rust_error_result::parse_config;
+///
+/// let config = parse_config("# tides\nmoon = full\n").unwrap();
+/// assert_eq!(config, vec![("moon".to_string(), "full".to_string())]);
+///
+/// let err = parse_config("moon full").unwrap_err();
+/// assert_eq!(err.to_string(), "config error at 1:10: expected '='");
+/// ```
+pub fn parse_config(text: &str) -> Result<Vec<(String, String)>, ConfigError> {
+    let mut entries = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let error = |col, message: &str| ConfigError {
+            line: i + 1,
+            col,
+            message: message.to_string(),
+        };
+        let (key, value) = match line.split_once('=') {
+            Some(pair) => pair,
+            None => {
+                let end = line.trim_end().chars().count();
+                return Err(error(end + 1, "expected '='"));
+            }
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            let eq_col = line.chars().take_while(|&c| c != '=').count() + 1;
+            return Err(error(eq_col, "missing key before '='"));
+        }
+        entries.push((key.to_string(), value.trim().to_string()));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_formed_config() {
+        let text = "# weather\nlat = 34.05\n\n  lng=-118.24  \nunits =\n";
+        let config = parse_config(text).unwrap();
+        let pairs: Vec<(&str, &str)> = config
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(pairs, [("lat", "34.05"), ("lng", "-118.24"), ("units", "")]);
+    }
+
+    #[test]
+    fn missing_equals_is_reported_at_end_of_line() {
+        let err = parse_config("lat = 34.05\nlng -118.24  \n").unwrap_err();
+        assert_eq!(
+            err,
+            ConfigError {
+                line: 2,
+                col: 12,
+                message: "expected '='".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn empty_key_is_reported_at_equals() {
+        let err = parse_config("lat = 34.05\n\n   = -118.24\n").unwrap_err();
+        assert_eq!((err.line, err.col), (3, 4));
+        assert_eq!(
+            err.to_string(),
+            "config error at 3:4: missing key before '='"
+        );
+    }
+}
@@ -0,0 +1,25 @@
+// Errors without std
+
+// std::error::Error lives in std, so it isn't available to #![no_std] code.
+// With the "std" feature off, CoreError stands in for it: the same Display
+// and Debug output, and the same optional source().
+//
+// `Error` names whichever trait is active, and the helpers that only walk or
+// print a chain (chain(), root_cause(), format_error()) are written against
+// it, so they work either way.
+
+use core::fmt::{Debug, Display};
+
+/// A minimal stand-in for `std::error::Error`.
+pub trait CoreError: Debug + Display {
+    /// The underlying error, if any, that triggered this one.
+    fn source(&self) -> Option<&dyn CoreError> {
+        None
+    }
+}
+
+#[cfg(feature = "std")]
+pub use std::error::Error;
+
+#[cfg(not(feature = "std"))]
+pub use self::CoreError as Error;
@@ -0,0 +1,134 @@
+// Handling One Kind of Error
+
+// If we're calling a function that returns a GenResult, and we want to handle
+// one particular kind of error but let all others propagate out, we can use
+// err.downcast_ref::<ErrorType>(). handle_or_rethrow() packages up that
+// pattern.
+
+use crate::chain::static_chain;
+use crate::prelude::{GenError, GenResult};
+use std::error::Error;
+
+/// Handle `err` with `f` if it is an `E`; otherwise return it unchanged.
+///
+/// ```
+/// use rust_error_result::downcast::handle_or_rethrow;
+/// use rust_error_result::GenError;
+/// use std::num::ParseIntError;
+///
+/// let err = GenError::from("bleen".parse::<u64>().unwrap_err());
+/// let fallback = handle_or_rethrow(err, |_: &ParseIntError| Ok(0));
+/// assert_eq!(fallback.unwrap(), 0);
+/// ```
+pub fn handle_or_rethrow<T, E, F>(err: GenError, f: F) -> GenResult<T>
+where
+    E: Error + 'static,
+    F: FnOnce(&E) -> GenResult<T>,
+{
+    match err.downcast_ref::<E>() {
+        Some(concrete) => f(concrete),
+        None => Err(err),
+    }
+}
+
+/// Take the concrete error out of `err`, if it is an `E`; otherwise return
+/// `err` unchanged.
+///
+/// `E` doesn't need to be `Send + Sync` itself. A `GenError` can only hold
+/// errors that are, so if the downcast succeeds, `E` must be one of them.
+///
+/// ```
+/// use rust_error_result::downcast::downcast_owned;
+/// use rust_error_result::GenError;
+/// use std::io;
+///
+/// let err = GenError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+/// let io_err: io::Error = downcast_owned(err).unwrap();
+/// assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+/// ```
+pub fn downcast_owned<E: Error + 'static>(err: GenError) -> Result<E, GenError> {
+    err.downcast::<E>().map(|concrete| *concrete)
+}
+
+/// The root cause of `err`, if it is an `E`.
+///
+/// This finds the same error as [`root_cause`](crate::chain::root_cause).
+/// Only errors with no borrowed data can be downcast, so `err` must be
+/// `'static`.
+///
+/// ```
+/// use rust_error_result::downcast::downcast_root;
+/// use std::io;
+///
+/// let err = io::Error::new(io::ErrorKind::TimedOut, "timed out");
+/// let root = downcast_root::<io::Error>(&err).unwrap();
+/// assert_eq!(root.kind(), io::ErrorKind::TimedOut);
+/// ```
+pub fn downcast_root<'a, E: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a E> {
+    static_chain(err).last()?.downcast_ref::<E>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ContextError;
+    use std::io;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn matching_error_is_handled() {
+        let err = GenError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let result = handle_or_rethrow(err, |e: &io::Error| Ok(e.kind()));
+        assert_eq!(result.unwrap(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn other_errors_are_rethrown_unchanged() {
+        let err = GenError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let addr = &*err as *const _ as *const u8;
+        let result: GenResult<()> = handle_or_rethrow(err, |_: &ParseIntError| {
+            panic!("handler called for the wrong error type")
+        });
+
+        let err = result.unwrap_err();
+        assert_eq!(&*err as *const _ as *const u8, addr);
+        let io_err = err.downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn downcast_owned_takes_the_error() {
+        let err = GenError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let io_err: io::Error = downcast_owned(err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(io_err.into_inner().unwrap().to_string(), "timed out");
+    }
+
+    #[test]
+    fn downcast_owned_failure_returns_the_box() {
+        let err = GenError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let addr = &*err as *const _ as *const u8;
+
+        let err = downcast_owned::<ParseIntError>(err).unwrap_err();
+        assert_eq!(&*err as *const _ as *const u8, addr);
+        assert_eq!(err.to_string(), "timed out");
+    }
+
+    #[test]
+    fn downcast_root_finds_deepest_error() {
+        let root = io::Error::new(io::ErrorKind::TimedOut, "timed out");
+        let middle = ContextError {
+            context: "fetching weather".to_string(),
+            source: Box::new(root),
+        };
+        let top = ContextError {
+            context: "planning picnic".to_string(),
+            source: Box::new(middle),
+        };
+
+        let io_err = downcast_root::<io::Error>(&top).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+        assert!(downcast_root::<ParseIntError>(&top).is_none());
+        assert!(downcast_root::<ContextError>(&top).is_none());
+    }
+}
@@ -0,0 +1,331 @@
+// Printing Errors
+
+// Printing an error value does not also print out its cause. print_error()
+// walks the whole chain so all the available info ends up on stderr.
+// write_error() does the same for any writer, such as a log file. With the
+// "color" feature, the labels can be colored for a terminal. A MultiError
+// has more than one cause, so print_error_tree() draws it as a tree. When a
+// library wraps an error without adding anything, print_error_dedup() skips
+// the repeated message.
+
+use crate::chain::{chain, MAX_DEPTH};
+use crate::multi::MultiError;
+use std::error::Error;
+#[cfg(feature = "color")]
+use std::io::IsTerminal;
+use std::io::{self, stderr, Write};
+
+/// Dump an error msg to `stderr`.
+///
+/// If another error happens while building the error msg or
+/// writing to `stderr`, it is ignored.
+///
+/// With the "color" feature, the labels are colored if `stderr` is a
+/// terminal.
+pub fn print_error(err: &dyn Error) {
+    #[cfg(feature = "color")]
+    let _ = write_error_colored(&mut stderr(), err, stderr().is_terminal());
+    #[cfg(not(feature = "color"))]
+    let _ = write_error(&mut stderr(), err);
+}
+
+/// Write an error msg, followed by each of its causes, to `w`.
+///
+/// ```
+/// use rust_error_result::write_error;
+/// use std::io;
+///
+/// let mut out = Vec::new();
+/// write_error(&mut out, &io::Error::other("moon not found")).unwrap();
+/// assert_eq!(out, b"error: moon not found\n");
+/// ```
+pub fn write_error<W: Write>(w: &mut W, err: &dyn Error) -> io::Result<()> {
+    write_labelled(w, err, "error:", "caused by:")
+}
+
+/// Like [`write_error`], but if `use_color` is true, "error:" is red and
+/// "caused by:" is yellow, using ANSI escape codes.
+///
+/// This never checks whether `w` is a terminal; that's up to the caller.
+#[cfg(feature = "color")]
+pub fn write_error_colored<W: Write>(
+    w: &mut W,
+    err: &dyn Error,
+    use_color: bool,
+) -> io::Result<()> {
+    if use_color {
+        write_labelled(w, err, "\x1b[31merror:\x1b[0m", "\x1b[33mcaused by:\x1b[0m")
+    } else {
+        write_error(w, err)
+    }
+}
+
+/// Like [`write_error`], but if a cause prints exactly the same message as
+/// the error just before it, it is left out.
+///
+/// Only consecutive repeats are collapsed; the same message further down
+/// the chain is still written.
+pub fn print_error_dedup<W: Write>(w: &mut W, err: &dyn Error) -> io::Result<()> {
+    let mut last = err.to_string();
+    writeln!(w, "error: {}", last)?;
+    for cause in chain(err).skip(1) {
+        let message = cause.to_string();
+        if message != last {
+            writeln!(w, "caused by: {}", message)?;
+            last = message;
+        }
+    }
+    Ok(())
+}
+
+/// Write an error as a tree, showing every branch of a [`MultiError`].
+///
+/// Each cause of a `MultiError` is written as a "- " branch, indented one
+/// level below it. Other errors are followed by their chain of causes as
+/// usual, each one indented one more level. Downcasting to `MultiError`
+/// needs a `'static` error.
+///
+/// Like [`chain`](crate::chain::chain), the tree stops after
+/// [`MAX_DEPTH`] levels, in case a chain loops back on itself.
+///
+/// ```
+/// use rust_error_result::{err, print_error_tree, MultiError};
+///
+/// let invalid = MultiError::new("invalid form", vec![err!("name is empty")]);
+/// let mut out = Vec::new();
+/// print_error_tree(&mut out, &invalid).unwrap();
+/// assert_eq!(out, b"error: invalid form\n  - name is empty\n");
+/// ```
+pub fn print_error_tree<W: Write>(w: &mut W, err: &(dyn Error + 'static)) -> io::Result<()> {
+    write_tree(w, err, "error:", 0)
+}
+
+fn write_tree<W: Write>(
+    w: &mut W,
+    err: &(dyn Error + 'static),
+    label: &str,
+    depth: usize,
+) -> io::Result<()> {
+    let indent = depth * 2;
+    if depth == MAX_DEPTH {
+        return writeln!(w, "{:indent$}... (truncated)", "", indent = indent);
+    }
+    if let Some(multi) = err.downcast_ref::<MultiError>() {
+        writeln!(
+            w,
+            "{:indent$}{} {}",
+            "",
+            label,
+            multi.message(),
+            indent = indent
+        )?;
+        for cause in multi.causes() {
+            write_tree(w, &**cause, "-", depth + 1)?;
+        }
+    } else {
+        writeln!(w, "{:indent$}{} {}", "", label, err, indent = indent)?;
+        if let Some(source) = err.source() {
+            write_tree(w, source, "caused by:", depth + 1)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_labelled<W: Write>(
+    w: &mut W,
+    err: &dyn Error,
+    error_label: &str,
+    cause_label: &str,
+) -> io::Result<()> {
+    writeln!(w, "{} {}", error_label, err)?;
+    for cause in chain(err).skip(1) {
+        writeln!(w, "{} {}", cause_label, cause)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+    use std::io;
+
+    #[derive(Debug)]
+    struct Wrapped {
+        message: &'static str,
+        source: Box<dyn Error + Send + Sync>,
+    }
+
+    impl fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.message)
+        }
+    }
+
+    impl Error for Wrapped {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&*self.source)
+        }
+    }
+
+    #[test]
+    fn prints_whole_chain_in_order() {
+        let root = io::Error::other("network outage");
+        let middle = Wrapped {
+            message: "failed to transfer $300 to United Yacht Supply",
+            source: Box::new(root),
+        };
+        let top = Wrapped {
+            message: "boat was repossessed",
+            source: Box::new(middle),
+        };
+
+        let mut buf = Vec::new();
+        write_error(&mut buf, &top).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "error: boat was repossessed\n\
+             caused by: failed to transfer $300 to United Yacht Supply\n\
+             caused by: network outage\n"
+        );
+    }
+
+    #[test]
+    fn single_error_has_no_causes() {
+        let err = io::Error::other("moon not found");
+        let mut buf = Vec::new();
+        write_error(&mut buf, &err).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "error: moon not found\n");
+    }
+
+    #[test]
+    fn two_level_chain_bytes() {
+        let top = Wrapped {
+            message: "calculating tides",
+            source: Box::new(io::Error::other("moon not found")),
+        };
+        let mut buf = Vec::new();
+        write_error(&mut buf, &top).unwrap();
+        assert_eq!(
+            buf,
+            b"error: calculating tides\ncaused by: moon not found\n"
+        );
+    }
+
+    #[test]
+    fn write_failures_are_propagated() {
+        struct Broken;
+
+        impl Write for Broken {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let err = io::Error::other("moon not found");
+        let write_err = write_error(&mut Broken, &err).unwrap_err();
+        assert_eq!(write_err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn dedup_skips_repeated_levels() {
+        let root = io::Error::other("network outage");
+        let rewrapped = Wrapped {
+            message: "network outage",
+            source: Box::new(root),
+        };
+        let middle = Wrapped {
+            message: "failed to fetch weather",
+            source: Box::new(rewrapped),
+        };
+        let top = Wrapped {
+            message: "failed to fetch weather",
+            source: Box::new(middle),
+        };
+
+        let mut buf = Vec::new();
+        print_error_dedup(&mut buf, &top).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "error: failed to fetch weather\n\
+             caused by: network outage\n"
+        );
+    }
+
+    #[test]
+    fn tree_shows_each_branch() {
+        let chained = Wrapped {
+            message: "could not read config",
+            source: Box::new(io::Error::other("file not found")),
+        };
+        let invalid = MultiError::new(
+            "invalid settings",
+            vec![crate::err!("name is empty"), Box::new(chained)],
+        );
+
+        let mut buf = Vec::new();
+        print_error_tree(&mut buf, &invalid).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "error: invalid settings\n\
+             \x20 - name is empty\n\
+             \x20 - could not read config\n\
+             \x20   caused by: file not found\n"
+        );
+    }
+
+    #[test]
+    fn tree_of_plain_chain_is_indented() {
+        let top = Wrapped {
+            message: "calculating tides",
+            source: Box::new(io::Error::other("moon not found")),
+        };
+        let mut buf = Vec::new();
+        print_error_tree(&mut buf, &top).unwrap();
+        assert_eq!(
+            buf,
+            b"error: calculating tides\n  caused by: moon not found\n"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn colors_only_when_asked() {
+        let top = Wrapped {
+            message: "calculating tides",
+            source: Box::new(io::Error::other("moon not found")),
+        };
+
+        let mut colored = Vec::new();
+        write_error_colored(&mut colored, &top, true).unwrap();
+        assert_eq!(
+            String::from_utf8(colored).unwrap(),
+            "\x1b[31merror:\x1b[0m calculating tides\n\
+             \x1b[33mcaused by:\x1b[0m moon not found\n"
+        );
+
+        let mut plain = Vec::new();
+        write_error_colored(&mut plain, &top, false).unwrap();
+        assert!(!plain.contains(&0x1b));
+        assert_eq!(
+            plain,
+            b"error: calculating tides\ncaused by: moon not found\n"
+        );
+    }
+
+    #[test]
+    fn tree_of_cyclic_chain_is_truncated() {
+        let mut out = Vec::new();
+        print_error_tree(&mut out, &crate::chain::tests::Cyclic).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), MAX_DEPTH + 1);
+        assert_eq!(lines[0], "error: caused by itself");
+        assert_eq!(lines[MAX_DEPTH].trim_start(), "... (truncated)");
+    }
+}
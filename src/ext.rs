@@ -0,0 +1,625 @@
+// Adding Context to Errors
+
+// Passing a raw io::Error up through many layers loses track of what we were
+// doing when it happened. .context() wraps the error in a ContextError that
+// says what we were doing, and keeps the original as its source(), so
+// print_error() shows both.
+
+use crate::error_print::write_error;
+use crate::fields::{with_field, FieldedError};
+use crate::hook::run_error_hook;
+use crate::http::HttpError;
+use crate::prelude::{GenError, GenResult};
+use crate::redact::RedactedError;
+use crate::traced::TracedError;
+use std::backtrace::Backtrace;
+use std::error::Error;
+use std::fmt;
+use std::io::{stderr, Write};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An error with a message describing what was being attempted.
+///
+/// Displays only the context; the original error is available from
+/// `source()`.
+#[derive(Debug)]
+pub struct ContextError {
+    pub context: String,
+    pub source: GenError,
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.context)
+    }
+}
+
+impl Error for ContextError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Extra methods for `Result`.
+pub trait ResultExt<T, E> {
+    /// Wrap the error, if any, in a [`ContextError`] with the message `ctx`.
+    ///
+    /// ```
+    /// use rust_error_result::{get_weather, LatLng};
+    /// use rust_error_result::ext::ResultExt;
+    ///
+    /// let hometown = LatLng { lat: 91.0, lng: 0.0 };
+    /// let err = get_weather(hometown)
+    ///     .context("fetching hometown weather")
+    ///     .unwrap_err();
+    /// assert_eq!(err.to_string(), "fetching hometown weather");
+    /// assert!(err.source().is_some());
+    /// ```
+    fn context<C: fmt::Display>(self, ctx: C) -> GenResult<T>;
+
+    /// Like [`context`](ResultExt::context), but `f` builds the message
+    /// only if there is an error. Use this when the message is costly to
+    /// build, for example with `format!`.
+    fn with_context<C, F>(self, f: F) -> GenResult<T>
+    where
+        C: fmt::Display,
+        F: FnOnce() -> C;
+
+    /// Like [`context`](ResultExt::context), but only if `pred` is true
+    /// for the error. Otherwise the error is boxed as it is.
+    ///
+    /// ```
+    /// use rust_error_result::ext::ResultExt;
+    /// use std::io;
+    ///
+    /// let failed: io::Result<()> = Err(io::ErrorKind::NotFound.into());
+    /// let err = failed
+    ///     .context_if(|e| e.kind() == io::ErrorKind::NotFound, "reading tides.txt")
+    ///     .unwrap_err();
+    /// assert_eq!(err.to_string(), "reading tides.txt");
+    /// ```
+    fn context_if<C, P>(self, pred: P, ctx: C) -> GenResult<T>
+    where
+        C: fmt::Display,
+        P: FnOnce(&E) -> bool;
+
+    /// Call `f` with the error, if any, and return `self` unchanged.
+    ///
+    /// Handy for logging or counting failures in the middle of a chain of
+    /// calls, without taking the error out of the `Result`.
+    fn tap_err<F: FnOnce(&E)>(self, f: F) -> Result<T, E>;
+
+    /// Call `f` with the success value, if any, and return `self`
+    /// unchanged.
+    fn tap_ok<F: FnOnce(&T)>(self, f: F) -> Result<T, E>;
+
+    /// Like [`tap_err`](ResultExt::tap_err), but `f` gets the error as a
+    /// `&dyn Error`, ready for walking its chain with `source()` or
+    /// [`chain`](crate::chain::chain).
+    fn inspect_chain<F: FnOnce(&dyn Error)>(self, f: F) -> Self;
+
+    /// Box the error, if any, into a [`GenError`], just as `?` would.
+    ///
+    /// Useful when a `GenResult` is needed as a value, rather than
+    /// returned with `?`.
+    fn boxed(self) -> GenResult<T>;
+
+    /// Both `.ok()` and `.err()` at once: `(Some(v), None)` for `Ok(v)`,
+    /// and `(None, Some(e))` for `Err(e)`.
+    fn split(self) -> (Option<T>, Option<E>);
+
+    /// Convert the error, if any, with `F::from`.
+    ///
+    /// The same as `.map_err(Into::into)`, but it names the target type
+    /// where `?` would leave it to be inferred.
+    fn err_into<F: From<E>>(self) -> Result<T, F>;
+
+    /// Attach the field `key`=`value` to the error, if any, wrapping it in
+    /// a [`FieldedError`]. If the error already is one, the field is added
+    /// to it instead, so fields accumulate through repeated calls.
+    fn field<K: Into<String>, V: fmt::Display>(self, key: K, value: V) -> Result<T, FieldedError>;
+
+    /// Like `.ok()`, but add one to `counter` if the error is dropped.
+    ///
+    /// For best-effort work, where errors are ignored but it's still worth
+    /// knowing how many there were.
+    fn ignore_but_count(self, counter: &AtomicUsize) -> Option<T>;
+
+    /// The success value, or `default` after passing the error to
+    /// `log_fn`.
+    ///
+    /// For errors that are worth a warning but not worth stopping for.
+    /// Unlike `let _ = ...`, the error can't be dropped without a trace.
+    fn warn_or<F: FnOnce(&E)>(self, default: T, log_fn: F) -> T;
+
+    /// Wrap the error, if any, in a [`RedactedError`] that masks each of
+    /// `patterns` in its messages, and those of its causes.
+    fn redact(self, patterns: Vec<String>) -> Result<T, RedactedError>;
+
+    /// Tag the error, if any, with the HTTP status `code`.
+    fn status(self, code: u16) -> Result<T, HttpError>;
+
+    /// Wrap the error, if any, in a [`TracedError`] with a stack trace of
+    /// this call.
+    ///
+    /// As with [`TracedError::new`], the trace is only collected if
+    /// `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` asks for it.
+    ///
+    /// ```
+    /// use rust_error_result::ext::ResultExt;
+    /// use rust_error_result::TracedError;
+    ///
+    /// fn parse_tide(s: &str) -> Result<u32, TracedError> {
+    ///     let height = s.parse::<u32>().traced()?;
+    ///     Ok(height)
+    /// }
+    ///
+    /// let err = parse_tide("bleen").unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid digit found in string");
+    /// ```
+    fn traced(self) -> Result<T, TracedError>;
+}
+
+impl<T, E> ResultExt<T, E> for Result<T, E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn context<C: fmt::Display>(self, ctx: C) -> GenResult<T> {
+        self.map_err(|err| {
+            run_error_hook(&err);
+            GenError::from(ContextError {
+                context: ctx.to_string(),
+                source: Box::new(err),
+            })
+        })
+    }
+
+    fn with_context<C, F>(self, f: F) -> GenResult<T>
+    where
+        C: fmt::Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|err| {
+            run_error_hook(&err);
+            GenError::from(ContextError {
+                context: f().to_string(),
+                source: Box::new(err),
+            })
+        })
+    }
+
+    fn context_if<C, P>(self, pred: P, ctx: C) -> GenResult<T>
+    where
+        C: fmt::Display,
+        P: FnOnce(&E) -> bool,
+    {
+        match self {
+            Err(err) if pred(&err) => {
+                run_error_hook(&err);
+                Err(ContextError {
+                    context: ctx.to_string(),
+                    source: Box::new(err),
+                }
+                .into())
+            }
+            other => other.boxed(),
+        }
+    }
+
+    fn tap_err<F: FnOnce(&E)>(self, f: F) -> Result<T, E> {
+        if let Err(err) = &self {
+            f(err);
+        }
+        self
+    }
+
+    fn tap_ok<F: FnOnce(&T)>(self, f: F) -> Result<T, E> {
+        if let Ok(value) = &self {
+            f(value);
+        }
+        self
+    }
+
+    fn inspect_chain<F: FnOnce(&dyn Error)>(self, f: F) -> Self {
+        if let Err(err) = &self {
+            f(err);
+        }
+        self
+    }
+
+    fn boxed(self) -> GenResult<T> {
+        self.map_err(|err| {
+            run_error_hook(&err);
+            GenError::from(err)
+        })
+    }
+
+    fn split(self) -> (Option<T>, Option<E>) {
+        match self {
+            Ok(value) => (Some(value), None),
+            Err(err) => (None, Some(err)),
+        }
+    }
+
+    fn err_into<F: From<E>>(self) -> Result<T, F> {
+        self.map_err(F::from)
+    }
+
+    fn field<K: Into<String>, V: fmt::Display>(self, key: K, value: V) -> Result<T, FieldedError> {
+        self.map_err(|err| with_field(GenError::from(err), key, value))
+    }
+
+    fn ignore_but_count(self, counter: &AtomicUsize) -> Option<T> {
+        match self {
+            Ok(value) => Some(value),
+            Err(_) => {
+                counter.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn warn_or<F: FnOnce(&E)>(self, default: T, log_fn: F) -> T {
+        match self {
+            Ok(value) => value,
+            Err(err) => {
+                log_fn(&err);
+                default
+            }
+        }
+    }
+
+    fn redact(self, patterns: Vec<String>) -> Result<T, RedactedError> {
+        self.map_err(|err| RedactedError::new(err, patterns))
+    }
+
+    fn status(self, code: u16) -> Result<T, HttpError> {
+        self.map_err(|err| HttpError::new(code, err))
+    }
+
+    fn traced(self) -> Result<T, TracedError> {
+        traced_using(self, Backtrace::capture)
+    }
+}
+
+fn traced_using<T, E>(result: Result<T, E>, capture: fn() -> Backtrace) -> Result<T, TracedError>
+where
+    E: Error + Send + Sync + 'static,
+{
+    result.map_err(|err| {
+        run_error_hook(&err);
+        TracedError::with_backtrace(Box::new(err), capture())
+    })
+}
+
+/// Extra methods for a nested `GenResult`.
+pub trait FlattenErr<T> {
+    /// Collapse `GenResult<GenResult<T>>` into `GenResult<T>`, keeping
+    /// whichever error happened, outer or inner.
+    fn flatten_err(self) -> GenResult<T>;
+}
+
+impl<T> FlattenErr<T> for GenResult<GenResult<T>> {
+    fn flatten_err(self) -> GenResult<T> {
+        match self {
+            Ok(inner) => inner,
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// [`ResultExt::context`] and [`ResultExt::with_context`] for a
+/// `GenResult`, whose boxed error doesn't implement `Error` itself.
+///
+/// The error is already boxed, so unlike the `ResultExt` methods, these
+/// don't pass it to the [error hook](crate::hook::set_error_hook) again.
+///
+/// ```
+/// use rust_error_result::ext::GenResultExt;
+/// use rust_error_result::{err, GenResult};
+///
+/// let failed: GenResult<()> = Err(err!("moon not found"));
+/// let err = failed.context("calculating tides").unwrap_err();
+/// assert_eq!(err.to_string(), "calculating tides");
+/// assert_eq!(err.source().unwrap().to_string(), "moon not found");
+/// ```
+pub trait GenResultExt<T> {
+    /// Wrap the error, if any, in a [`ContextError`] with the message `ctx`.
+    fn context<C: fmt::Display>(self, ctx: C) -> GenResult<T>;
+
+    /// Like [`context`](GenResultExt::context), but `f` builds the message
+    /// only if there is an error.
+    fn with_context<C, F>(self, f: F) -> GenResult<T>
+    where
+        C: fmt::Display,
+        F: FnOnce() -> C;
+}
+
+impl<T> GenResultExt<T> for GenResult<T> {
+    fn context<C: fmt::Display>(self, ctx: C) -> GenResult<T> {
+        self.with_context(|| ctx)
+    }
+
+    fn with_context<C, F>(self, f: F) -> GenResult<T>
+    where
+        C: fmt::Display,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|source| {
+            GenError::from(ContextError {
+                context: f().to_string(),
+                source,
+            })
+        })
+    }
+}
+
+/// Turn the result of a whole program into an exit code.
+///
+/// This is a separate trait from [`ResultExt`] so that it covers
+/// `GenResult<()>`, whose boxed error doesn't implement `Error` itself.
+///
+/// ```no_run
+/// use rust_error_result::ext::IntoExitCode;
+/// use rust_error_result::{read_numbers, GenResult};
+/// use std::io;
+/// use std::process::ExitCode;
+///
+/// fn run() -> GenResult<()> {
+///     let numbers = read_numbers(&mut io::stdin().lock())?;
+///     println!("{}", numbers.iter().sum::<i64>());
+///     Ok(())
+/// }
+///
+/// fn main() -> ExitCode {
+///     run().into_exit_code()
+/// }
+/// ```
+pub trait IntoExitCode {
+    /// `ExitCode::SUCCESS` for `Ok`. For `Err`, write the error and its
+    /// causes to `stderr` with [`write_error`], and return
+    /// `ExitCode::FAILURE`.
+    fn into_exit_code(self) -> ExitCode;
+}
+
+impl<E: Into<GenError>> IntoExitCode for Result<(), E> {
+    fn into_exit_code(self) -> ExitCode {
+        exit_code_writing(self.map_err(Into::into), &mut stderr())
+    }
+}
+
+fn exit_code_writing<W: Write>(result: GenResult<()>, w: &mut W) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let _ = write_error(w, &*err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apperror::AppError;
+    use crate::chain::chain;
+    use std::backtrace::BacktraceStatus;
+    use std::cell::Cell;
+    use std::io;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn with_context_is_lazy() {
+        let calls = Cell::new(0);
+        let message = || {
+            calls.set(calls.get() + 1);
+            format!("reading {}", "tides.txt")
+        };
+
+        let ok: io::Result<i32> = Ok(1);
+        assert_eq!(ok.with_context(message).unwrap(), 1);
+        assert_eq!(calls.get(), 0);
+
+        let failed: io::Result<i32> = Err(io::Error::other("disk on fire"));
+        let err = failed.with_context(message).unwrap_err();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(err.to_string(), "reading tides.txt");
+        assert_eq!(err.source().unwrap().to_string(), "disk on fire");
+    }
+
+    #[test]
+    fn tap_runs_only_on_matching_variant() {
+        let mut seen_err = None;
+        let mut seen_ok = None;
+        let ok: io::Result<i32> = Ok(7);
+        let ok = ok
+            .tap_err(|e| seen_err = Some(e.to_string()))
+            .tap_ok(|v| seen_ok = Some(*v));
+        assert_eq!(ok.unwrap(), 7);
+        assert_eq!(seen_ok, Some(7));
+        assert_eq!(seen_err, None);
+
+        let mut seen_err = None;
+        let mut seen_ok = None;
+        let failed: io::Result<i32> = Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let failed = failed
+            .tap_ok(|v| seen_ok = Some(*v))
+            .tap_err(|e| seen_err = Some(e.to_string()));
+        assert_eq!(failed.unwrap_err().kind(), io::ErrorKind::TimedOut);
+        assert_eq!(seen_err.as_deref(), Some("timed out"));
+        assert_eq!(seen_ok, None);
+    }
+
+    #[test]
+    fn flatten_err() {
+        let ok: GenResult<GenResult<i32>> = Ok(Ok(3));
+        assert_eq!(ok.flatten_err().unwrap(), 3);
+
+        let inner: GenResult<GenResult<i32>> = Ok(Err(crate::err!("inner")));
+        assert_eq!(inner.flatten_err().unwrap_err().to_string(), "inner");
+
+        let outer: GenResult<GenResult<i32>> = Err(crate::err!("outer"));
+        assert_eq!(outer.flatten_err().unwrap_err().to_string(), "outer");
+    }
+
+    #[test]
+    fn gen_result_context() {
+        let failed: GenResult<()> = Err(io::Error::other("moon not found").into());
+        let err = GenResultExt::context(failed, "calculating tides").unwrap_err();
+        assert_eq!(
+            crate::format::ChainDisplay(&*err).to_string(),
+            "calculating tides: moon not found"
+        );
+        assert!(crate::downcast_root::<io::Error>(&*err).is_some());
+
+        let ok: GenResult<i32> = Ok(3);
+        let value = GenResultExt::with_context(ok, || -> String { panic!("built a message") });
+        assert_eq!(value.unwrap(), 3);
+    }
+
+    #[test]
+    fn inspect_chain_sees_every_level() {
+        let failed: Result<(), ContextError> = Err(ContextError {
+            context: "fetching weather".to_string(),
+            source: Box::new(io::Error::new(io::ErrorKind::TimedOut, "timed out")),
+        });
+
+        let mut messages = vec![];
+        let failed = failed.inspect_chain(|err| {
+            messages.extend(chain(err).map(|e| e.to_string()));
+        });
+        assert_eq!(messages, vec!["fetching weather", "timed out"]);
+
+        let err = failed.unwrap_err();
+        assert_eq!(err.context, "fetching weather");
+        let io_err = err.source.downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+
+        let ok: io::Result<i32> = Ok(1);
+        assert_eq!(ok.inspect_chain(|_| panic!("called on Ok")).unwrap(), 1);
+    }
+
+    #[test]
+    fn boxed_keeps_the_error() {
+        let results: Vec<GenResult<i64>> = vec![
+            Err(io::Error::new(io::ErrorKind::TimedOut, "timed out")).boxed(),
+            "bleen".parse::<i64>().boxed(),
+            "7".parse::<i64>().boxed(),
+        ];
+
+        let io_err = results[0].as_ref().unwrap_err().downcast_ref::<io::Error>();
+        assert_eq!(io_err.unwrap().kind(), io::ErrorKind::TimedOut);
+        let parse_err = results[1].as_ref().unwrap_err();
+        assert!(parse_err.downcast_ref::<ParseIntError>().is_some());
+        assert_eq!(*results[2].as_ref().unwrap(), 7);
+    }
+
+    #[test]
+    fn split_gives_exactly_one_side() {
+        let (value, err) = "7".parse::<i64>().split();
+        assert_eq!(value, Some(7));
+        assert!(err.is_none());
+
+        let (value, err) = "bleen".parse::<i64>().split();
+        assert_eq!(value, None);
+        assert_eq!(err.unwrap().to_string(), "invalid digit found in string");
+    }
+
+    #[test]
+    fn err_into_converts_explicitly() {
+        let app = "bleen".parse::<i64>().err_into::<AppError>();
+        assert!(matches!(app, Err(AppError::Parse(_))));
+
+        let gen = "bleen".parse::<i64>().err_into::<GenError>();
+        assert!(gen.unwrap_err().downcast_ref::<ParseIntError>().is_some());
+
+        let ok = "7".parse::<i64>().err_into::<AppError>();
+        assert_eq!(ok.unwrap(), 7);
+    }
+
+    #[test]
+    fn ignore_but_count_counts_dropped_errors() {
+        let dropped = AtomicUsize::new(0);
+        let kept: Vec<i64> = ["1", "bleen", "3", "", "5"]
+            .iter()
+            .filter_map(|s| s.parse::<i64>().ignore_but_count(&dropped))
+            .collect();
+        assert_eq!(kept, vec![1, 3, 5]);
+        assert_eq!(dropped.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn context_if_wraps_only_matching_errors() {
+        let is_not_found = |e: &io::Error| e.kind() == io::ErrorKind::NotFound;
+
+        let missing: io::Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        let err = missing
+            .context_if(is_not_found, "reading tides.txt")
+            .unwrap_err();
+        assert_eq!(chain(&*err).count(), 2);
+        assert_eq!(err.to_string(), "reading tides.txt");
+
+        let denied: io::Result<()> = Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        let err = denied
+            .context_if(is_not_found, "reading tides.txt")
+            .unwrap_err();
+        assert_eq!(chain(&*err).count(), 1);
+        assert_eq!(
+            err.downcast_ref::<io::Error>().unwrap().kind(),
+            io::ErrorKind::PermissionDenied
+        );
+
+        let ok: io::Result<i32> = Ok(1);
+        assert_eq!(
+            ok.context_if(|_| panic!("called on Ok"), "unused").unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn exit_code_for_each_branch() {
+        let mut out = Vec::new();
+        assert_eq!(exit_code_writing(Ok(()), &mut out), ExitCode::SUCCESS);
+        assert!(out.is_empty());
+
+        let failed: io::Result<()> = Err(io::Error::other("network outage"));
+        let code = exit_code_writing(failed.context("fetching weather"), &mut out);
+        assert_eq!(code, ExitCode::FAILURE);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "error: fetching weather\ncaused by: network outage\n"
+        );
+
+        let ok: GenResult<()> = Ok(());
+        assert_eq!(ok.into_exit_code(), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn warn_or_logs_and_defaults() {
+        let warnings = Cell::new(0);
+        let warn = |err: &ParseIntError| {
+            warnings.set(warnings.get() + 1);
+            assert_eq!(err.to_string(), "invalid digit found in string");
+        };
+
+        assert_eq!("bleen".parse::<i64>().warn_or(0, warn), 0);
+        assert_eq!(warnings.get(), 1);
+
+        assert_eq!("7".parse::<i64>().warn_or(0, |_| panic!("called on Ok")), 7);
+        assert_eq!(warnings.get(), 1);
+    }
+
+    #[test]
+    fn traced_captures_backtrace() {
+        let failed: io::Result<()> = Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let err = traced_using(failed, Backtrace::force_capture).unwrap_err();
+        assert_eq!(err.backtrace().status(), BacktraceStatus::Captured);
+        assert_eq!(err.to_string(), "timed out");
+        let io_err = err.into_inner().downcast::<io::Error>().unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+
+        let ok: io::Result<i32> = Ok(7);
+        assert_eq!(ok.traced().unwrap(), 7);
+    }
+}
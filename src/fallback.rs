@@ -0,0 +1,79 @@
+// Fallbacks
+
+// unwrap_or_else() gives one fallback. Sometimes there are several places to
+// get a value from, best first: the live weather service, then the cache,
+// then a vague prediction. first_ok() tries each in turn.
+
+/// Run each of `ops` in order until one succeeds, and return its value. If
+/// they all fail, return the last error.
+///
+/// Panics if `ops` is empty, since then there's neither a value nor an
+/// error to return.
+///
+/// ```
+/// use rust_error_result::{first_ok, get_weather, vague_prediction, LatLng, WeatherError};
+///
+/// let nowhere = LatLng { lat: 91.0, lng: 0.0 };
+/// let report = first_ok::<_, WeatherError, _>(vec![
+///     Box::new(|| get_weather(nowhere)) as Box<dyn FnOnce() -> _>,
+///     Box::new(|| Ok(vague_prediction(nowhere))),
+/// ]);
+/// assert_eq!(report.unwrap(), vague_prediction(nowhere));
+/// ```
+pub fn first_ok<'a, T, E, I>(ops: I) -> Result<T, E>
+where
+    I: IntoIterator<Item = Box<dyn FnOnce() -> Result<T, E> + 'a>>,
+{
+    let mut last_err = None;
+    for op in ops {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.expect("first_ok needs at least one alternative"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn third_alternative_succeeds() {
+        let calls = Cell::new(0);
+        let count = || calls.set(calls.get() + 1);
+        let ops: Vec<Box<dyn FnOnce() -> Result<i32, &'static str> + '_>> = vec![
+            Box::new(|| {
+                count();
+                Err("weather service is down")
+            }),
+            Box::new(|| {
+                count();
+                Err("cache is empty")
+            }),
+            Box::new(|| {
+                count();
+                Ok(72)
+            }),
+            Box::new(|| panic!("ran past the first success")),
+        ];
+        assert_eq!(first_ok(ops), Ok(72));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn all_failing_returns_last_error() {
+        let ops: Vec<Box<dyn FnOnce() -> Result<i32, &'static str>>> = vec![
+            Box::new(|| Err("weather service is down")),
+            Box::new(|| Err("cache is empty")),
+        ];
+        assert_eq!(first_ok(ops), Err("cache is empty"));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one alternative")]
+    fn no_alternatives_panics() {
+        let _ = first_ok::<i32, &'static str, _>(vec![]);
+    }
+}
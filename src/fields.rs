@@ -0,0 +1,154 @@
+// Error Fields
+
+// For logs and metrics, it helps to know more than what went wrong: which
+// request it was, which user. FieldedError carries key/value fields along
+// with an error as it propagates, without changing its message.
+
+use crate::prelude::GenError;
+use crate::transparent::Transparent;
+use std::error::Error;
+use std::fmt;
+
+/// An error with key/value fields attached.
+///
+/// Displays as the wrapped error does, followed by the fields in brackets.
+/// Its `source()` is the wrapped error's own, so the message isn't printed
+/// twice, and it's [`Transparent`], so the wrapped error can still be
+/// downcast.
+///
+/// Use [`ResultExt::field`](crate::ext::ResultExt::field) to add fields;
+/// adding one to a `FieldedError` adds to its list instead of wrapping it
+/// again.
+#[derive(Debug)]
+pub struct FieldedError {
+    source: GenError,
+    fields: Vec<(String, String)>,
+}
+
+impl FieldedError {
+    pub fn new<E: Into<GenError>>(err: E) -> FieldedError {
+        FieldedError {
+            source: err.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// The fields, in the order they were added.
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// The value of the field named `key`, if there is one. If it was
+    /// added more than once, this is the latest value.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn push<K: Into<String>, V: fmt::Display>(&mut self, key: K, value: V) {
+        self.fields.push((key.into(), value.to_string()));
+    }
+
+    pub fn into_inner(self) -> GenError {
+        self.source
+    }
+}
+
+impl fmt::Display for FieldedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.source, f)?;
+        if !self.fields.is_empty() {
+            write!(f, " [")?;
+            for (i, (key, value)) in self.fields.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}={}", key, value)?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for FieldedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.source()
+    }
+}
+
+impl Transparent for FieldedError {
+    fn wrapped(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Wrap `err` in a [`FieldedError`], unless it already is one.
+pub(crate) fn with_field<K: Into<String>, V: fmt::Display>(
+    err: GenError,
+    key: K,
+    value: V,
+) -> FieldedError {
+    let mut fielded = match err.downcast::<FieldedError>() {
+        Ok(fielded) => *fielded,
+        Err(err) => FieldedError::new(err),
+    };
+    fielded.push(key, value);
+    fielded
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ext::ResultExt;
+    use std::io;
+
+    #[test]
+    fn fields_accumulate_without_rewrapping() {
+        let failed: io::Result<()> = Err(io::Error::other("network outage"));
+        let err = failed
+            .field("request_id", "abc123")
+            .field("user_id", 42)
+            .unwrap_err();
+
+        assert_eq!(
+            err.fields(),
+            [
+                ("request_id".to_string(), "abc123".to_string()),
+                ("user_id".to_string(), "42".to_string()),
+            ]
+        );
+        assert_eq!(err.get("user_id"), Some("42"));
+        assert_eq!(
+            err.to_string(),
+            "network outage [request_id=abc123, user_id=42]"
+        );
+
+        // Only one layer of wrapping: the inner error is the io::Error.
+        let inner = err.into_inner();
+        assert!(inner.downcast_ref::<io::Error>().is_some());
+    }
+
+    #[test]
+    fn fields_keep_error_reachable() {
+        let missing: io::Result<()> = Err(io::ErrorKind::NotFound.into());
+        let err = missing
+            .field("path", "tides.csv")
+            .context("loading tide table")
+            .unwrap_err();
+        assert_eq!(crate::categorize(&*err), crate::ErrorCategory::NotFound);
+        let io_err = crate::downcast_root::<io::Error>(&*err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn chain_shows_message_once() {
+        let failed: io::Result<()> = Err(io::Error::other("network outage"));
+        let err = failed.field("request_id", "abc123").unwrap_err();
+        let mut out = Vec::new();
+        crate::write_error(&mut out, &err).unwrap();
+        assert_eq!(out, b"error: network outage [request_id=abc123]\n");
+    }
+}
@@ -0,0 +1,108 @@
+// File Ages
+
+// last_modified.elapsed() can fail only if the system time is earlier than
+// last_modified. That can happen if the file was created recently and the
+// system clock was adjusted backward while our program was running. Rather
+// than panic with .expect("system clock drift"), print_file_age() treats
+// the file as brand new, and with the "log" feature, logs a warning about it.
+// The current time comes from a Clock, so the tests don't depend on when they
+// run.
+
+use crate::clock::{Clock, SystemClock};
+use crate::humanize::humanize_duration;
+use std::io::{self, stdout, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Print `filename` and how long ago it was last modified to `stdout`, like
+/// "tides.txt: modified 3 days ago".
+///
+/// If `last_modified` is in the future, the age is taken to be zero. With
+/// the "log" feature, a warning about clock drift is logged. Without it,
+/// the drift goes unreported.
+pub fn print_file_age(filename: &Path, last_modified: SystemTime) -> io::Result<()> {
+    write_file_age(&mut stdout(), &SystemClock, filename, last_modified)
+}
+
+fn write_file_age<W: Write, C: Clock>(
+    out: &mut W,
+    clock: &C,
+    filename: &Path,
+    last_modified: SystemTime,
+) -> io::Result<()> {
+    let elapsed = clock.system_now().duration_since(last_modified);
+    let age = elapsed.unwrap_or_else(|err| {
+        #[cfg(feature = "log")]
+        log::warn!(
+            "{} was modified {:?} in the future (system clock drift?)",
+            filename.display(),
+            err.duration()
+        );
+        // Nowhere to report it; see print_file_age().
+        #[cfg(not(feature = "log"))]
+        let _ = err;
+        Duration::from_secs(0)
+    });
+    if age == Duration::from_secs(0) {
+        writeln!(out, "{}: modified just now", filename.display())
+    } else {
+        writeln!(
+            out,
+            "{}: modified {} ago",
+            filename.display(),
+            humanize_duration(age)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FakeClock;
+
+    #[test]
+    fn future_time_is_age_zero() {
+        let clock = FakeClock::new();
+        let future = clock.system_now() + Duration::from_secs(3600);
+        let mut out = Vec::new();
+        write_file_age(&mut out, &clock, Path::new("tides.txt"), future).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "tides.txt: modified just now\n"
+        );
+
+        assert!(print_file_age(Path::new("tides.txt"), future).is_ok());
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn future_time_logs_warning() {
+        crate::logging::test_logger::install();
+        let clock = FakeClock::new();
+        let future = clock.system_now() + Duration::from_secs(3600);
+        let mut out = Vec::new();
+        write_file_age(&mut out, &clock, Path::new("drift_test.txt"), future).unwrap();
+
+        let records = crate::logging::test_logger::records_containing("drift_test.txt");
+        assert_eq!(
+            records,
+            [(
+                log::Level::Warn,
+                "drift_test.txt was modified 3600s in the future (system clock drift?)".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn past_time() {
+        let clock = FakeClock::new();
+        let past = clock.system_now();
+        clock.advance(Duration::from_secs(90));
+        let mut out = Vec::new();
+        write_file_age(&mut out, &clock, Path::new("tides.txt"), past).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "tides.txt: modified 1 minute ago\n"
+        );
+    }
+}
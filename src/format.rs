@@ -0,0 +1,298 @@
+// Display vs Debug
+
+// Printing an error with the {} format specifier typically displays only a
+// brief error message. Printing with {:?} gets a Debug view of the error,
+// which is less user-friendly but includes extra technical info.
+// format_error() gives either one, and the verbose form also includes every
+// error in the source chain. ChainDisplay puts the whole chain on one line.
+// user_facing() goes the other way, hiding the causes from end users, and
+// TruncatedError keeps an enormous message from flooding a log.
+
+use crate::chain::chain;
+use crate::core_error::Error;
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write};
+
+/// Format `err` for a user (`verbose == false`) or for a developer
+/// (`verbose == true`).
+///
+/// The brief form is just `err`'s `Display` output. The verbose form has
+/// the `Debug` output of `err` and each of its sources on separate lines,
+/// each cause indented under the error it caused.
+///
+/// ```
+/// use rust_error_result::format::format_error;
+/// use std::io;
+///
+/// let err = io::Error::new(io::ErrorKind::NotFound, "moon not found");
+/// assert_eq!(format_error(&err, false), "moon not found");
+/// assert_eq!(
+///     format_error(&err, true),
+///     r#"Custom { kind: NotFound, error: "moon not found" }"#
+/// );
+/// ```
+pub fn format_error(err: &dyn Error, verbose: bool) -> String {
+    if !verbose {
+        return err.to_string();
+    }
+    let mut out = String::new();
+    for (depth, cause) in chain(err).enumerate() {
+        if depth > 0 {
+            let _ = write!(out, "\n{:width$}caused by: ", "", width = 2 * depth);
+        }
+        let _ = write!(out, "{:?}", cause);
+    }
+    out
+}
+
+/// Format `err` for an end user: just its own message, without the
+/// technical details of its causes.
+///
+/// If `err` has a cause, " (see logs for details)" is added, and with the
+/// "log" feature, the whole chain is logged at error level.
+///
+/// ```
+/// use rust_error_result::format::user_facing;
+/// use rust_error_result::ext::ResultExt;
+/// use std::io;
+///
+/// let failed: io::Result<()> = Err(io::Error::other("connection reset"));
+/// let err = failed.context("could not save your picnic plan").unwrap_err();
+/// assert_eq!(
+///     user_facing(&*err),
+///     "could not save your picnic plan (see logs for details)"
+/// );
+/// ```
+pub fn user_facing(err: &dyn Error) -> String {
+    if err.source().is_none() {
+        return err.to_string();
+    }
+    #[cfg(feature = "log")]
+    log::error!("{}", ChainDisplay(err));
+    let mut out = err.to_string();
+    out.push_str(" (see logs for details)");
+    out
+}
+
+/// Displays an error and all its causes on one line, separated by ": ".
+///
+/// For some other separator, use [`ChainDisplayWith`].
+///
+/// ```
+/// use rust_error_result::format::ChainDisplay;
+/// use rust_error_result::ext::ResultExt;
+/// use std::io;
+///
+/// let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+/// let err = failed.context("calculating tides").unwrap_err();
+/// assert_eq!(
+///     format!("{}", ChainDisplay(&*err)),
+///     "calculating tides: moon not found"
+/// );
+/// ```
+pub struct ChainDisplay<'a>(pub &'a dyn Error);
+
+impl fmt::Display for ChainDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&ChainDisplayWith::new(self.0, ": "), f)
+    }
+}
+
+/// Displays an error and all its causes, separated by `sep`.
+///
+/// ```
+/// use rust_error_result::format::ChainDisplayWith;
+/// use rust_error_result::ext::ResultExt;
+/// use std::io;
+///
+/// let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+/// let err = failed.context("calculating tides").unwrap_err();
+/// assert_eq!(
+///     ChainDisplayWith::new(&*err, " -> ").to_string(),
+///     "calculating tides -> moon not found"
+/// );
+/// ```
+pub struct ChainDisplayWith<'a> {
+    err: &'a dyn Error,
+    sep: &'a str,
+}
+
+impl<'a> ChainDisplayWith<'a> {
+    pub fn new(err: &'a dyn Error, sep: &'a str) -> ChainDisplayWith<'a> {
+        ChainDisplayWith { err, sep }
+    }
+}
+
+impl fmt::Display for ChainDisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, cause) in chain(self.err).enumerate() {
+            if i > 0 {
+                f.write_str(self.sep)?;
+            }
+            write!(f, "{}", cause)?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays an error's message, cut off after `max` characters.
+///
+/// A message that's too long ends with "… (N more chars)". Characters are
+/// counted as `char`s, so the cut never splits a UTF-8 sequence.
+///
+/// ```
+/// use rust_error_result::format::TruncatedError;
+/// use std::io;
+///
+/// let err = io::Error::other("unexpected token at byte 1048576");
+/// assert_eq!(
+///     TruncatedError::new(&err, 16).to_string(),
+///     "unexpected token… (16 more chars)"
+/// );
+/// ```
+pub struct TruncatedError<'a> {
+    err: &'a dyn Error,
+    max: usize,
+}
+
+impl<'a> TruncatedError<'a> {
+    pub fn new(err: &'a dyn Error, max: usize) -> TruncatedError<'a> {
+        TruncatedError { err, max }
+    }
+}
+
+impl fmt::Display for TruncatedError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = self.err.to_string();
+        match message.char_indices().nth(self.max) {
+            None => f.write_str(&message),
+            Some((cut, _)) => {
+                let rest = message[cut..].chars().count();
+                write!(f, "{}… ({} more chars)", &message[..cut], rest)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::ext::{ContextError, ResultExt};
+    use std::io;
+
+    #[test]
+    fn brief_and_verbose_forms() {
+        let failed: io::Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "moon not found"));
+        let err = failed.context("calculating tides").unwrap_err();
+
+        assert_eq!(format_error(&*err, false), "calculating tides");
+        assert_eq!(
+            format_error(&*err, true),
+            "ContextError { context: \"calculating tides\", source: Custom { kind: NotFound, error: \"moon not found\" } }\n  \
+             caused by: Custom { kind: NotFound, error: \"moon not found\" }"
+        );
+    }
+
+    #[test]
+    fn user_facing_hides_causes() {
+        let single = io::Error::other("moon not found");
+        assert_eq!(user_facing(&single), "moon not found");
+
+        let failed: io::Result<()> = Err(io::Error::other("user_facing test: network outage"));
+        let err = failed.context("could not fetch weather").unwrap_err();
+        assert_eq!(
+            user_facing(&*err),
+            "could not fetch weather (see logs for details)"
+        );
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn user_facing_logs_whole_chain() {
+        crate::logging::test_logger::install();
+        let failed: io::Result<()> = Err(io::Error::other("user_facing log test: outage"));
+        let err = failed.context("could not fetch weather").unwrap_err();
+        user_facing(&*err);
+
+        let records = crate::logging::test_logger::records_containing("user_facing log test");
+        let messages: Vec<&str> = records.iter().map(|(_, m)| m.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["could not fetch weather: user_facing log test: outage"]
+        );
+    }
+
+    #[test]
+    fn chain_display_levels() {
+        let root = || io::Error::other("network outage");
+        let middle = || ContextError {
+            context: "failed to transfer $300".to_string(),
+            source: Box::new(root()),
+        };
+        let top = ContextError {
+            context: "boat was repossessed".to_string(),
+            source: Box::new(middle()),
+        };
+
+        assert_eq!(ChainDisplay(&root()).to_string(), "network outage");
+        assert_eq!(
+            ChainDisplay(&middle()).to_string(),
+            "failed to transfer $300: network outage"
+        );
+        assert_eq!(
+            ChainDisplay(&top).to_string(),
+            "boat was repossessed: failed to transfer $300: network outage"
+        );
+    }
+
+    #[test]
+    fn chain_display_with_separator() {
+        let top = ContextError {
+            context: "top".to_string(),
+            source: Box::new(ContextError {
+                context: "middle".to_string(),
+                source: Box::new(io::Error::other("root")),
+            }),
+        };
+
+        assert_eq!(ChainDisplayWith::new(&top, "").to_string(), "topmiddleroot");
+
+        let rendered = ChainDisplayWith::new(&top, " -> ").to_string();
+        assert_eq!(rendered, "top -> middle -> root");
+        assert_eq!(rendered.matches(" -> ").count(), 2);
+
+        let alone = io::Error::other("root");
+        assert_eq!(ChainDisplayWith::new(&alone, "\n").to_string(), "root");
+    }
+
+    #[test]
+    fn truncates_long_ascii_message() {
+        let err = io::Error::other("x".repeat(1000));
+        assert_eq!(
+            TruncatedError::new(&err, 5).to_string(),
+            "xxxxx… (995 more chars)"
+        );
+    }
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        // Each 'é' is two bytes, and each '🌊' is four.
+        let err = io::Error::other("éé🌊🌊🌊");
+        assert_eq!(
+            TruncatedError::new(&err, 3).to_string(),
+            "éé🌊… (2 more chars)"
+        );
+        assert_eq!(
+            TruncatedError::new(&err, 1).to_string(),
+            "é… (4 more chars)"
+        );
+    }
+
+    #[test]
+    fn short_message_is_untouched() {
+        let err = io::Error::other("moon not found");
+        assert_eq!(TruncatedError::new(&err, 14).to_string(), "moon not found");
+        assert_eq!(TruncatedError::new(&err, 100).to_string(), "moon not found");
+    }
+}
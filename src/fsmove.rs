@@ -0,0 +1,123 @@
+// Moving Files
+
+// Errors in code that interfaces with the OS can be pervasive. Bailing out
+// with ? on the first failed rename leaves a move half done, so move_all()
+// keeps going and reports every entry that failed.
+
+use crate::io_convert::io_error_with_path;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The entries that [`move_all`] failed to move, and why.
+#[derive(Debug)]
+pub struct MoveErrors {
+    failures: Vec<(PathBuf, io::Error)>,
+}
+
+impl MoveErrors {
+    /// Each path that couldn't be moved, with the error that stopped it.
+    pub fn failures(&self) -> &[(PathBuf, io::Error)] {
+        &self.failures
+    }
+
+    pub fn into_failures(self) -> Vec<(PathBuf, io::Error)> {
+        self.failures
+    }
+}
+
+impl fmt::Display for MoveErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.failures.len() {
+            1 => write!(f, "failed to move 1 entry"),
+            n => write!(f, "failed to move {} entries", n),
+        }
+    }
+}
+
+impl Error for MoveErrors {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.failures
+            .first()
+            .map(|(_, err)| err as &(dyn Error + 'static))
+    }
+}
+
+/// Move every entry in the directory `src` into the directory `dst`.
+///
+/// A failure to move one entry doesn't stop the others from being moved.
+/// Each error's message ends with the path it happened on.
+/// Returns `Ok(())` only if every entry moved.
+pub fn move_all(src: &Path, dst: &Path) -> Result<(), MoveErrors> {
+    let mut failures = vec![];
+    match src.read_dir() {
+        Ok(entries) => {
+            for entry_result in entries {
+                let entry = match entry_result {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        failures.push((src.to_path_buf(), io_error_with_path(err, src)));
+                        continue;
+                    }
+                };
+                let dst_file = dst.join(entry.file_name());
+                if let Err(err) = fs::rename(entry.path(), dst_file) {
+                    let path = entry.path();
+                    let err = io_error_with_path(err, &path);
+                    failures.push((path, err));
+                }
+            }
+        }
+        Err(err) => failures.push((src.to_path_buf(), io_error_with_path(err, src))),
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(MoveErrors { failures })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn one_bad_entry_does_not_stop_the_rest() {
+        let root = env::temp_dir().join(format!("move_all_test_{}", process::id()));
+        let src = root.join("src");
+        let dst = root.join("dst");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        fs::write(src.join("b.txt"), "b").unwrap();
+        fs::write(src.join("c.txt"), "c").unwrap();
+
+        // A non-empty directory can't be replaced by a file, even by root,
+        // for whom a read-only destination would be no obstacle.
+        fs::create_dir_all(dst.join("b.txt").join("blocker")).unwrap();
+
+        let errors = move_all(&src, &dst).unwrap_err();
+        assert_eq!(errors.to_string(), "failed to move 1 entry");
+        assert_eq!(errors.failures().len(), 1);
+        assert_eq!(errors.failures()[0].0, src.join("b.txt"));
+        assert!(dst.join("a.txt").is_file());
+        assert!(dst.join("c.txt").is_file());
+        assert!(src.join("b.txt").is_file());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn missing_source_dir_is_reported() {
+        let src = Path::new("/no/such/dir/for/move_all");
+        let errors = move_all(src, &env::temp_dir()).unwrap_err();
+        let err = &errors.failures()[0].1;
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().ends_with(": /no/such/dir/for/move_all"));
+    }
+}
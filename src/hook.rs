@@ -0,0 +1,61 @@
+// Error Hooks
+
+// A panic hook sees every panic in one place. An error hook does the same for
+// errors: once one is set, the ResultExt combinators that box an error, such
+// as context() and boxed(), pass it to the hook first. That's a single place
+// to count errors for metrics, without changing any call sites.
+
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+type Hook = Arc<dyn Fn(&dyn Error) + Send + Sync>;
+
+static HOOK: Mutex<Option<Hook>> = Mutex::new(None);
+
+/// Call `f` with every error boxed by [`ResultExt`](crate::ext::ResultExt)'s
+/// `context`, `with_context`, `context_if` and `boxed`, replacing any hook
+/// set before.
+///
+/// `f` sees the original error, before it's wrapped or boxed. The hook is
+/// global, and may be called from many threads at once.
+pub fn set_error_hook(f: impl Fn(&dyn Error) + Send + Sync + 'static) {
+    *HOOK.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(f));
+}
+
+/// Pass `err` to the hook, if one is set.
+pub(crate) fn run_error_hook(err: &dyn Error) {
+    // Call the hook without holding the lock, so that it can use the
+    // combinators itself.
+    let hook = HOOK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    if let Some(hook) = hook {
+        hook(err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ResultExt;
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn hook_fires_once_per_boxed_error() {
+        // Other tests box errors at the same time, so only count ours.
+        static SEEN: AtomicUsize = AtomicUsize::new(0);
+        set_error_hook(|err| {
+            if err.to_string().starts_with("hook test") {
+                SEEN.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let fail = || -> io::Result<()> { Err(io::Error::other("hook test: outage")) };
+        let _ = fail().boxed();
+        let _ = fail().context("fetching weather");
+        let _ = Ok::<(), io::Error>(()).boxed();
+        assert_eq!(SEEN.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,138 @@
+// HTTP Status Codes
+
+// A web handler has to turn every error into a response, and the status code
+// depends on what went wrong. HttpError tags an error with the status to
+// respond with, and status_of() finds it again, however deeply the error
+// ends up wrapped.
+
+use crate::chain::static_chain;
+use crate::prelude::GenError;
+use crate::transparent::Transparent;
+use std::error::Error;
+use std::fmt;
+
+/// The status for errors that weren't tagged with one: 500 Internal Server
+/// Error.
+pub const DEFAULT_STATUS: u16 = 500;
+
+/// An error tagged with an HTTP status code.
+///
+/// Displays as the wrapped error does, and passes its `source()` through;
+/// see [`Transparent`].
+#[derive(Debug)]
+pub struct HttpError {
+    status: u16,
+    source: GenError,
+}
+
+impl HttpError {
+    pub fn new<E: Into<GenError>>(status: u16, err: E) -> HttpError {
+        HttpError {
+            status,
+            source: err.into(),
+        }
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn into_inner(self) -> GenError {
+        self.source
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.source, f)
+    }
+}
+
+impl Error for HttpError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.source()
+    }
+}
+
+impl Transparent for HttpError {
+    fn wrapped(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// The status of the first [`HttpError`] in `err`'s chain, or
+/// [`DEFAULT_STATUS`] if there isn't one.
+///
+/// ```
+/// use rust_error_result::ext::ResultExt;
+/// use rust_error_result::status_of;
+/// use std::io;
+///
+/// let missing: io::Result<()> = Err(io::ErrorKind::NotFound.into());
+/// let err = missing.status(404).context("loading tide table").unwrap_err();
+/// assert_eq!(status_of(&*err), 404);
+/// ```
+pub fn status_of(err: &(dyn Error + 'static)) -> u16 {
+    static_chain(err)
+        .find_map(|e| e.downcast_ref::<HttpError>())
+        .map_or(DEFAULT_STATUS, HttpError::status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::Transient;
+    use crate::ext::ResultExt;
+    use std::io;
+
+    #[test]
+    fn tagged_error_has_its_status() {
+        let missing: io::Result<()> = Err(io::Error::new(io::ErrorKind::NotFound, "no such tide"));
+        let err = missing.status(404).unwrap_err();
+        assert_eq!(err.status(), 404);
+        assert_eq!(err.to_string(), "no such tide");
+        assert_eq!(status_of(&err), 404);
+
+        let wrapped = Err::<(), _>(err).context("handling /tides").unwrap_err();
+        assert_eq!(status_of(&*wrapped), 404);
+    }
+
+    #[test]
+    fn untagged_chain_is_500() {
+        let failed: io::Result<()> = Err(io::Error::other("network outage"));
+        let err = failed.context("fetching weather").unwrap_err();
+        assert_eq!(status_of(&*err), 500);
+    }
+
+    #[test]
+    fn wrapped_error_stays_reachable() {
+        let missing: io::Result<()> = Err(io::ErrorKind::NotFound.into());
+        let err = missing
+            .status(404)
+            .context("loading tide table")
+            .unwrap_err();
+        assert_eq!(status_of(&*err), 404);
+        assert_eq!(crate::categorize(&*err), crate::ErrorCategory::NotFound);
+        let io_err = crate::downcast_root::<io::Error>(&*err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+
+        let timed_out: io::Result<()> = Err(io::ErrorKind::TimedOut.into());
+        let err = timed_out
+            .status(504)
+            .context("fetching weather")
+            .unwrap_err();
+        assert!(err.is_transient());
+    }
+
+    #[test]
+    fn chain_shows_message_once() {
+        let failed: io::Result<()> = Err(io::Error::other("weather service down"));
+        let err = failed.status(503).context("fetching weather").unwrap_err();
+        let mut out = Vec::new();
+        crate::write_error(&mut out, &*err).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "error: fetching weather\ncaused by: weather service down\n"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// Human-Readable Durations
+
+// A raw Duration prints as something like 93784.5s, which is no good in an
+// error message. humanize_duration() rounds down to the largest whole unit
+// instead: "1 day", "26 hours" and so on.
+
+use std::time::Duration;
+
+/// Describe `d` in its largest whole unit, from days down to milliseconds.
+///
+/// A zero duration is "just now", and anything else under a millisecond is
+/// "less than a millisecond".
+///
+/// ```
+/// use rust_error_result::humanize::humanize_duration;
+/// use std::time::Duration;
+///
+/// let msg = format!("timed out after {}", humanize_duration(Duration::from_secs(30)));
+/// assert_eq!(msg, "timed out after 30 seconds");
+/// ```
+pub fn humanize_duration(d: Duration) -> String {
+    const UNITS: [(u64, &str); 4] = [
+        (86400, "day"),
+        (3600, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ];
+
+    if d == Duration::from_secs(0) {
+        return "just now".to_string();
+    }
+    let secs = d.as_secs();
+    if d < Duration::from_millis(1) {
+        return "less than a millisecond".to_string();
+    }
+    if secs == 0 {
+        return plural(d.as_millis() as u64, "millisecond");
+    }
+    for &(size, unit) in UNITS.iter() {
+        if secs >= size {
+            return plural(secs / size, unit);
+        }
+    }
+    unreachable!("secs is at least 1")
+}
+
+fn plural(n: u64, unit: &str) -> String {
+    if n == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", n, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magnitudes() {
+        let table = [
+            (Duration::from_secs(0), "just now"),
+            (Duration::from_nanos(1), "less than a millisecond"),
+            (Duration::from_micros(999), "less than a millisecond"),
+            (Duration::from_millis(1), "1 millisecond"),
+            (Duration::from_millis(250), "250 milliseconds"),
+            (Duration::from_secs(1), "1 second"),
+            (Duration::from_secs(59), "59 seconds"),
+            (Duration::from_secs(60), "1 minute"),
+            (Duration::from_secs(90), "1 minute"),
+            (Duration::from_secs(5 * 3600), "5 hours"),
+            (Duration::from_secs(86400), "1 day"),
+            (Duration::from_secs(3 * 86400 + 7), "3 days"),
+        ];
+        for &(d, expected) in table.iter() {
+            assert_eq!(humanize_duration(d), expected, "{:?}", d);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// Building io::Errors
+
+// io::Error::new(io::ErrorKind::Other, "timed out") is a mouthful, and it
+// has no way to say what caused the error. IoErrorBuilder fills in the
+// kind for us, and can attach a cause that the built error's source() will
+// return.
+
+use crate::prelude::GenError;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Builds an `io::Error` one piece at a time.
+///
+/// ```
+/// use rust_error_result::io_builder::IoErrorBuilder;
+/// use std::io;
+///
+/// let err = IoErrorBuilder::new()
+///     .kind(io::ErrorKind::TimedOut)
+///     .message("timed out")
+///     .build();
+/// assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+/// assert_eq!(err.to_string(), "timed out");
+/// ```
+#[derive(Debug)]
+pub struct IoErrorBuilder {
+    kind: io::ErrorKind,
+    message: Option<String>,
+    cause: Option<GenError>,
+}
+
+impl Default for IoErrorBuilder {
+    fn default() -> IoErrorBuilder {
+        IoErrorBuilder {
+            kind: io::ErrorKind::Other,
+            message: None,
+            cause: None,
+        }
+    }
+}
+
+impl IoErrorBuilder {
+    /// A builder for an error of kind `Other`, with no message or cause.
+    pub fn new() -> IoErrorBuilder {
+        IoErrorBuilder::default()
+    }
+
+    pub fn kind(mut self, kind: io::ErrorKind) -> IoErrorBuilder {
+        self.kind = kind;
+        self
+    }
+
+    pub fn message<S: Into<String>>(mut self, message: S) -> IoErrorBuilder {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// The error that caused this one. With a message, it's the built
+    /// error's `source()`; see [`build`](IoErrorBuilder::build).
+    pub fn cause<E: Error + Send + Sync + 'static>(mut self, cause: E) -> IoErrorBuilder {
+        self.cause = Some(Box::new(cause));
+        self
+    }
+
+    /// Build the error.
+    ///
+    /// With a cause but no message, the cause itself is the error inside
+    /// the `io::Error`, reachable with `get_ref()`: the built error displays
+    /// as the cause does, and its `source()` is the cause's `source()`, so
+    /// the message isn't repeated. With neither, the error is just its kind.
+    pub fn build(self) -> io::Error {
+        match (self.message, self.cause) {
+            (Some(message), Some(cause)) => io::Error::new(self.kind, Caused { message, cause }),
+            (Some(message), None) => io::Error::new(self.kind, message),
+            (None, Some(cause)) => io::Error::new(self.kind, cause),
+            (None, None) => io::Error::from(self.kind),
+        }
+    }
+}
+
+// The error inside an io::Error with both a message and a cause. io::Error
+// displays this error, and its source() is this error's source().
+#[derive(Debug)]
+struct Caused {
+    message: String,
+    cause: GenError,
+}
+
+impl fmt::Display for Caused {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for Caused {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.cause)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn defaults_to_other() {
+        let err = IoErrorBuilder::new().message("timed out").build();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.to_string(), "timed out");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn cause_becomes_source() {
+        let parse_err = "bleen".parse::<u64>().unwrap_err();
+        let err = IoErrorBuilder::new()
+            .kind(io::ErrorKind::InvalidData)
+            .message("bad config")
+            .cause(parse_err.clone())
+            .build();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "bad config");
+        let source = err.source().unwrap().downcast_ref::<ParseIntError>();
+        assert_eq!(source, Some(&parse_err));
+    }
+
+    #[test]
+    fn cause_without_message() {
+        let parse_err = "bleen".parse::<u64>().unwrap_err();
+        let err = IoErrorBuilder::new()
+            .kind(io::ErrorKind::InvalidData)
+            .cause(parse_err.clone())
+            .build();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "invalid digit found in string");
+        assert!(err.source().is_none());
+        let cause = err.get_ref().unwrap().downcast_ref::<ParseIntError>();
+        assert_eq!(cause, Some(&parse_err));
+    }
+
+    #[test]
+    fn kind_only() {
+        let err = IoErrorBuilder::new().kind(io::ErrorKind::NotFound).build();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,139 @@
+// Back to io::Error
+
+// Some APIs insist on an io::Result. into_io_error() funnels a GenError back
+// into an io::Error without losing anything: an io::Error comes back out as
+// itself, and anything else is wrapped so it stays reachable. Errors from
+// std::fs don't say which path they're about, so with_path() adds it.
+
+use crate::prelude::{GenError, GenResult};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Convert `err` to an `io::Error`.
+///
+/// If `err` is an `io::Error`, it is unboxed and returned as is, kind and
+/// all. Any other error is wrapped in an `io::Error` of kind `Other`, which
+/// displays the same message and can give the original back with
+/// `get_ref()` or `into_inner()`.
+pub fn into_io_error(err: GenError) -> io::Error {
+    match err.downcast::<io::Error>() {
+        Ok(io_err) => *io_err,
+        Err(other) => io::Error::other(other),
+    }
+}
+
+/// An `io::Error` along with the path it happened on.
+///
+/// Displays as "{error}: {path}". The original `io::Error` is `source()`.
+#[derive(Debug)]
+pub struct PathError {
+    path: PathBuf,
+    source: io::Error,
+}
+
+impl PathError {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.source, self.path.display())
+    }
+}
+
+impl Error for PathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Add `path` to the error, if any, as a [`PathError`].
+///
+/// The original `io::Error`, and so its kind, can still be had from the
+/// chain, for example with [`downcast_root`](crate::downcast::downcast_root).
+///
+/// ```
+/// use rust_error_result::{downcast_root, with_path};
+/// use std::fs::File;
+/// use std::io;
+/// use std::path::Path;
+///
+/// let path = Path::new("/no/such/tides.txt");
+/// let err = with_path(File::open(path), path).unwrap_err();
+/// assert!(err.to_string().ends_with(": /no/such/tides.txt"));
+/// let io_err = downcast_root::<io::Error>(&*err).unwrap();
+/// assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+/// ```
+pub fn with_path<T>(result: io::Result<T>, path: &Path) -> GenResult<T> {
+    result.map_err(|source| {
+        GenError::from(PathError {
+            path: path.to_path_buf(),
+            source,
+        })
+    })
+}
+
+/// Like [`with_path`], but still an `io::Error`, of the same kind as `err`.
+pub(crate) fn io_error_with_path(err: io::Error, path: &Path) -> io::Error {
+    let kind = err.kind();
+    io::Error::new(
+        kind,
+        PathError {
+            path: path.to_path_buf(),
+            source: err,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::downcast::downcast_root;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn io_error_round_trips() {
+        let err = GenError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let io_err = into_io_error(err);
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(io_err.to_string(), "timed out");
+    }
+
+    #[test]
+    fn other_errors_are_wrapped() {
+        let err = GenError::from("bleen".parse::<u64>().unwrap_err());
+        let io_err = into_io_error(err);
+        assert_eq!(io_err.kind(), io::ErrorKind::Other);
+        assert_eq!(io_err.to_string(), "invalid digit found in string");
+        let inner = io_err.get_ref().unwrap();
+        assert!(inner.downcast_ref::<ParseIntError>().is_some());
+    }
+
+    #[test]
+    fn with_path_names_the_path() {
+        let path = Path::new("/no/such/dir/tides.txt");
+        let err = with_path(std::fs::read(path), path).unwrap_err();
+        assert!(err.to_string().ends_with(": /no/such/dir/tides.txt"));
+        assert_eq!(err.downcast_ref::<PathError>().unwrap().path(), path);
+
+        let io_err = downcast_root::<io::Error>(&*err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn io_error_with_path_keeps_kind() {
+        let path = Path::new("tides.txt");
+        let err = io_error_with_path(
+            io::Error::new(io::ErrorKind::PermissionDenied, "denied"),
+            path,
+        );
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(err.to_string(), "denied: tides.txt");
+        let io_err = downcast_root::<io::Error>(&err).unwrap();
+        assert_eq!(io_err.to_string(), "denied");
+    }
+}
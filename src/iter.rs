@@ -0,0 +1,199 @@
+// Collecting Results
+
+// A loop that pushes each success value into a Vec and uses ? on each Result
+// is common enough to deserve a name. try_collect() stops at the first error.
+// partition_results() keeps going, and hands back the errors too. StopOnError
+// does what try_collect() does, but lazily, one item at a time.
+
+/// Collect the success values of `iter` into a `Vec`, or return the first
+/// error.
+///
+/// Items after the first error are not pulled from the iterator.
+pub fn try_collect<I, T, E>(iter: I) -> Result<Vec<T>, E>
+where
+    I: IntoIterator<Item = Result<T, E>>,
+{
+    let mut values = vec![];
+    try_collect_into(iter, &mut values)?;
+    Ok(values)
+}
+
+/// Like [`try_collect`], but append to `out` so its allocation can be
+/// reused across calls.
+///
+/// On error, `out` is truncated back to the length it had on entry, so it
+/// holds exactly what it held before the call.
+///
+/// ```
+/// use rust_error_result::iter::try_collect_into;
+///
+/// let mut numbers = vec![1];
+/// let result = try_collect_into(vec!["2", "bleen"].into_iter().map(str::parse), &mut numbers);
+/// assert!(result.is_err());
+/// assert_eq!(numbers, vec![1]);
+/// ```
+pub fn try_collect_into<I, T, E>(iter: I, out: &mut Vec<T>) -> Result<(), E>
+where
+    I: IntoIterator<Item = Result<T, E>>,
+{
+    let len = out.len();
+    for item in iter {
+        match item {
+            Ok(value) => out.push(value),
+            Err(err) => {
+                out.truncate(len);
+                return Err(err);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Split `iter` into its success values and its errors, each in their
+/// original order.
+///
+/// ```
+/// use rust_error_result::iter::partition_results;
+///
+/// let (numbers, errors) = partition_results(vec!["1", "bleen", "3"].into_iter().map(str::parse::<i64>));
+/// assert_eq!(numbers, vec![1, 3]);
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub fn partition_results<I, T, E>(iter: I) -> (Vec<T>, Vec<E>)
+where
+    I: IntoIterator<Item = Result<T, E>>,
+{
+    let mut values = vec![];
+    let mut errors = vec![];
+    for item in iter {
+        match item {
+            Ok(value) => values.push(value),
+            Err(err) => errors.push(err),
+        }
+    }
+    (values, errors)
+}
+
+/// An iterator over the success values of `inner` that stops at the first
+/// error, and keeps it to be looked at afterward with
+/// [`error`](StopOnError::error).
+///
+/// ```
+/// use rust_error_result::iter::StopOnError;
+///
+/// let mut numbers = StopOnError::new(vec!["1", "2", "bleen", "4"].into_iter().map(str::parse::<i64>));
+/// let mut total = 0;
+/// for n in numbers.by_ref() {
+///     total += n;
+/// }
+/// assert_eq!(total, 3);
+/// let (index, err) = numbers.error().unwrap();
+/// assert_eq!(index, 2);
+/// assert_eq!(err.to_string(), "invalid digit found in string");
+/// ```
+#[derive(Debug)]
+pub struct StopOnError<I, E> {
+    inner: I,
+    err: Option<E>,
+    count: usize,
+}
+
+impl<I, E> StopOnError<I, E> {
+    pub fn new(inner: I) -> StopOnError<I, E> {
+        StopOnError {
+            inner,
+            err: None,
+            count: 0,
+        }
+    }
+
+    /// The error that stopped the iteration, with its index among the
+    /// items of `inner`, or `None` if there wasn't one (yet).
+    pub fn error(self) -> Option<(usize, E)> {
+        let count = self.count;
+        self.err.map(|err| (count, err))
+    }
+}
+
+impl<I, T, E> Iterator for StopOnError<I, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.err.is_some() {
+            return None;
+        }
+        match self.inner.next()? {
+            Ok(value) => {
+                self.count += 1;
+                Some(value)
+            }
+            Err(err) => {
+                self.err = Some(err);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_all_successes() {
+        let results: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2), Ok(3)];
+        assert_eq!(try_collect(results), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        let mut pulled = 0;
+        let results = vec![Ok(1), Err("first"), Ok(3), Err("second")]
+            .into_iter()
+            .inspect(|_| pulled += 1);
+        assert_eq!(try_collect(results), Err("first"));
+        assert_eq!(pulled, 2);
+    }
+
+    #[test]
+    fn into_appends_on_success_and_restores_on_error() {
+        let mut out = vec![0];
+        assert_eq!(
+            try_collect_into(vec![Ok::<_, &str>(1), Ok(2)], &mut out),
+            Ok(())
+        );
+        assert_eq!(out, vec![0, 1, 2]);
+
+        assert_eq!(
+            try_collect_into(vec![Ok(3), Err("bad")], &mut out),
+            Err("bad")
+        );
+        assert_eq!(out, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn partition_keeps_order_within_groups() {
+        let results = vec![Ok(1), Err("a"), Ok(2), Err("b"), Ok(3)];
+        assert_eq!(partition_results(results), (vec![1, 2, 3], vec!["a", "b"]));
+
+        let empty: Vec<Result<i32, &str>> = vec![];
+        assert_eq!(partition_results(empty), (vec![], vec![]));
+    }
+
+    #[test]
+    fn stop_on_error_remembers_where() {
+        let results: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2), Err("moon not found"), Ok(4)];
+        let mut items = StopOnError::new(results.into_iter());
+        let values: Vec<i32> = items.by_ref().collect();
+        assert_eq!(values, vec![1, 2]);
+        assert_eq!(items.next(), None);
+        assert_eq!(items.error(), Some((2, "moon not found")));
+
+        let mut clean = StopOnError::new(vec![Ok::<i32, &str>(1)].into_iter());
+        assert_eq!(clean.by_ref().count(), 1);
+        assert_eq!(clean.error(), None);
+    }
+}
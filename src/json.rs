@@ -0,0 +1,70 @@
+// Errors as JSON
+
+// A web service may need to report errors as JSON rather than print them.
+// error_to_json() nests each error in the chain inside the one it caused. The
+// error types don't need to implement Serialize; only their Display output
+// is used.
+
+use crate::chain::chain;
+use serde::Serialize;
+use std::error::Error;
+
+#[derive(Serialize)]
+struct JsonError {
+    message: String,
+    cause: Option<Box<JsonError>>,
+}
+
+/// Serialize `err` and its sources as nested JSON objects.
+///
+/// Each object has the error's `"message"` and its `"cause"`, which is
+/// `null` for the root cause.
+///
+/// ```
+/// use rust_error_result::json::error_to_json;
+/// use std::io;
+///
+/// let err = io::Error::other("moon not found");
+/// assert_eq!(error_to_json(&err), r#"{"message":"moon not found","cause":null}"#);
+/// ```
+pub fn error_to_json(err: &dyn Error) -> String {
+    let messages: Vec<String> = chain(err).map(|e| e.to_string()).collect();
+    let json = messages
+        .into_iter()
+        .rev()
+        .fold(None, |cause, message| {
+            Some(Box::new(JsonError { message, cause }))
+        })
+        .expect("a chain always includes the error itself");
+    serde_json::to_string(&json).expect("serializing strings can't fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ResultExt;
+    use serde_json::{json, Value};
+    use std::io;
+
+    #[test]
+    fn nests_causes() {
+        let failed: io::Result<()> = Err(io::Error::other("network outage"));
+        let err = failed.context("fetching weather").unwrap_err();
+        let value: Value = serde_json::from_str(&error_to_json(&*err)).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "message": "fetching weather",
+                "cause": { "message": "network outage", "cause": null }
+            })
+        );
+    }
+
+    #[test]
+    fn sourceless_error_has_null_cause() {
+        let err = io::Error::other("moon not found");
+        let value: Value = serde_json::from_str(&error_to_json(&err)).unwrap();
+        assert_eq!(value["message"], "moon not found");
+        assert!(value["cause"].is_null());
+    }
+}
@@ -0,0 +1,78 @@
+// Lazy Error Messages
+
+// Building an error is usually cheap, but formatting its message might not
+// be, and plenty of errors are thrown away unread by unwrap_or() and the
+// like. LazyError holds a closure that builds the message, and only calls it
+// when the error is actually displayed.
+
+use std::error::Error;
+use std::fmt;
+
+/// An error whose message is built by `f` each time it's displayed.
+///
+/// To box a `LazyError` into a [`GenError`](crate::GenError), `f` must be
+/// `Send + Sync + 'static`, so it can't borrow anything; move what it needs
+/// into it instead.
+///
+/// ```
+/// use rust_error_result::{GenError, LazyError};
+///
+/// let line = 12;
+/// let err = GenError::from(LazyError::new(move || format!("bad tide table at line {}", line)));
+/// assert_eq!(err.to_string(), "bad tide table at line 12");
+/// ```
+pub struct LazyError<F: Fn() -> String> {
+    f: F,
+}
+
+impl<F: Fn() -> String> LazyError<F> {
+    pub fn new(f: F) -> LazyError<F> {
+        LazyError { f }
+    }
+}
+
+impl<F: Fn() -> String> fmt::Display for LazyError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&(self.f)())
+    }
+}
+
+/// Closures aren't `Debug`, so this shows the message, which means it
+/// builds it too.
+impl<F: Fn() -> String> fmt::Debug for LazyError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LazyError").field(&(self.f)()).finish()
+    }
+}
+
+impl<F: Fn() -> String> Error for LazyError<F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::GenError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn lookup(calls: &Arc<AtomicUsize>) -> Result<i32, LazyError<impl Fn() -> String>> {
+        let calls = Arc::clone(calls);
+        Err(LazyError::new(move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            "moon not found".to_string()
+        }))
+    }
+
+    #[test]
+    fn message_is_built_only_when_displayed() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        assert_eq!(lookup(&calls).unwrap_or(72), 72);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let err = GenError::from(lookup(&calls).unwrap_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(err.to_string(), "moon not found");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(err.to_string(), "moon not found");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,219 @@
+// Error Handling
+
+// The notes in main.rs walk through Rust's approach to errors. This library
+// holds the pieces of those notes that are worth reusing for real.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "std")]
+pub mod apperror;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod catch;
+#[cfg(feature = "std")]
+pub mod category;
+pub mod chain;
+#[cfg(feature = "std")]
+pub mod classify;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(feature = "std")]
+pub mod coded;
+#[cfg(feature = "std")]
+pub mod collect;
+#[cfg(feature = "std")]
+pub mod compile;
+#[cfg(feature = "std")]
+pub mod config;
+pub mod core_error;
+#[cfg(feature = "std")]
+pub mod downcast;
+#[cfg(feature = "std")]
+pub mod error_print;
+#[cfg(feature = "std")]
+pub mod ext;
+#[cfg(feature = "std")]
+pub mod fallback;
+#[cfg(feature = "std")]
+pub mod fields;
+#[cfg(feature = "std")]
+pub mod fileage;
+pub mod format;
+#[cfg(feature = "std")]
+pub mod fsmove;
+#[cfg(feature = "std")]
+pub mod hook;
+#[cfg(feature = "std")]
+pub mod http;
+#[cfg(feature = "std")]
+pub mod humanize;
+#[cfg(feature = "std")]
+pub mod io_builder;
+#[cfg(feature = "std")]
+pub mod io_convert;
+#[cfg(feature = "std")]
+pub mod iter;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod lazy;
+#[cfg(feature = "log")]
+pub mod logging;
+#[cfg(feature = "std")]
+pub mod multi;
+#[cfg(feature = "std")]
+pub mod numbers;
+#[cfg(feature = "std")]
+pub mod optionext;
+#[cfg(feature = "std")]
+pub mod prelude;
+#[cfg(feature = "std")]
+pub mod redact;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
+pub mod retry;
+#[cfg(feature = "async")]
+pub mod retry_async;
+#[cfg(feature = "std")]
+pub mod run;
+#[cfg(feature = "std")]
+pub mod string_error;
+#[cfg(feature = "std")]
+pub mod testutil;
+#[cfg(feature = "std")]
+pub mod threads;
+#[cfg(feature = "std")]
+pub mod throttle;
+#[cfg(feature = "std")]
+pub mod timeout;
+#[cfg(feature = "std")]
+pub mod traced;
+#[cfg(feature = "std")]
+pub mod transparent;
+#[cfg(feature = "std")]
+pub mod validate;
+#[cfg(feature = "std")]
+pub mod weather;
+#[cfg(feature = "std")]
+pub mod wrap;
+
+#[cfg(feature = "std")]
+pub use apperror::{gen_into_app, AppError, AppResult};
+#[cfg(feature = "std")]
+pub use cache::ResultCache;
+#[cfg(feature = "std")]
+pub use catch::catch;
+#[cfg(feature = "std")]
+pub use category::{categorize, ErrorCategory};
+#[cfg(feature = "std")]
+pub use chain::chain_contains;
+pub use chain::{chain, chain_len, root_cause, ErrorChain};
+#[cfg(feature = "std")]
+pub use classify::{is_transient, Transient};
+#[cfg(feature = "std")]
+pub use clock::{Clock, FakeClock, SystemClock};
+#[cfg(feature = "std")]
+pub use coded::{CodedError, CodedResultExt};
+#[cfg(feature = "std")]
+pub use collect::{CollectedErrors, ErrorCollection};
+#[cfg(feature = "std")]
+pub use compile::{build_until_success, compile_project, MissingSemicolonError, Project};
+#[cfg(feature = "std")]
+pub use config::{parse_config, ConfigError};
+#[cfg(feature = "std")]
+pub use downcast::{downcast_owned, downcast_root, handle_or_rethrow};
+#[cfg(feature = "color")]
+pub use error_print::write_error_colored;
+#[cfg(feature = "std")]
+pub use error_print::{print_error, print_error_dedup, print_error_tree, write_error};
+#[cfg(feature = "std")]
+pub use ext::{ContextError, FlattenErr, GenResultExt, IntoExitCode, ResultExt};
+#[cfg(feature = "std")]
+pub use fallback::first_ok;
+#[cfg(feature = "std")]
+pub use fields::FieldedError;
+#[cfg(feature = "std")]
+pub use fileage::print_file_age;
+pub use format::{format_error, user_facing, ChainDisplay, ChainDisplayWith, TruncatedError};
+#[cfg(feature = "std")]
+pub use fsmove::{move_all, MoveErrors};
+#[cfg(feature = "std")]
+pub use hook::set_error_hook;
+#[cfg(feature = "std")]
+pub use http::{status_of, HttpError};
+#[cfg(feature = "std")]
+pub use humanize::humanize_duration;
+#[cfg(feature = "std")]
+pub use io_builder::IoErrorBuilder;
+#[cfg(feature = "std")]
+pub use io_convert::{into_io_error, with_path, PathError};
+#[cfg(feature = "std")]
+pub use iter::{partition_results, try_collect, try_collect_into, StopOnError};
+#[cfg(feature = "serde")]
+pub use json::error_to_json;
+#[cfg(feature = "std")]
+pub use lazy::LazyError;
+#[cfg(feature = "log")]
+pub use logging::LogResultExt;
+#[cfg(feature = "std")]
+pub use multi::{combine, run_all, MultiError};
+#[cfg(feature = "std")]
+pub use numbers::{
+    read_numbers, read_numbers_all, read_numbers_as, read_numbers_from_path, LineParseError,
+    NumberLines,
+};
+#[cfg(feature = "std")]
+pub use optionext::OptionExt;
+#[cfg(feature = "std")]
+pub use prelude::{GenError, GenResult};
+#[cfg(feature = "std")]
+pub use redact::RedactedError;
+#[cfg(feature = "std")]
+pub use registry::ErrorRegistry;
+#[cfg(feature = "std")]
+pub use report::report_errors;
+#[cfg(feature = "std")]
+pub use retry::{retry, retry_with_backoff, retry_with_backoff_on, retry_within};
+#[cfg(feature = "async")]
+pub use retry_async::retry_async;
+#[cfg(feature = "std")]
+pub use run::main_wrapper;
+#[cfg(feature = "std")]
+pub use string_error::{string_error, MsgError, StringError};
+#[cfg(feature = "std")]
+pub use testutil::{assert_err, assert_ok, chain_messages, errors_match};
+#[cfg(feature = "std")]
+pub use threads::{join_all, spawn_collect};
+#[cfg(feature = "std")]
+pub use throttle::ErrorThrottle;
+#[cfg(feature = "std")]
+pub use timeout::{run_with_timeout, TimeoutError};
+#[cfg(feature = "std")]
+pub use traced::TracedError;
+#[cfg(feature = "std")]
+pub use transparent::{register_transparent, Transparent};
+#[cfg(feature = "std")]
+pub use validate::Validator;
+#[cfg(feature = "std")]
+pub use weather::{
+    display_weather, get_weather, vague_prediction, LatLng, LatLngParseError, WeatherError,
+    WeatherReport, WeatherResultExt, THE_USUAL,
+};
+#[cfg(feature = "std")]
+pub use wrap::{unnest, wrap, BoxedError, Wrapped};
+
+// Used by the macros that log.
+#[cfg(feature = "log")]
+#[doc(hidden)]
+pub use log as __log;
@@ -0,0 +1,118 @@
+// Logging Errors
+
+// Sometimes we want to propagate an error with ? but still leave a record of
+// it. log_err() logs the whole chain and hands the Result back unchanged, so
+// it can sit right in front of the ?.
+
+use crate::chain::chain;
+use std::error::Error;
+
+pub trait LogResultExt<T> {
+    /// Log the error, if any, at the error level, one record for each
+    /// error in its chain. Returns `self` unchanged.
+    ///
+    /// ```
+    /// use rust_error_result::logging::LogResultExt;
+    /// use rust_error_result::{get_weather, GenResult, LatLng};
+    ///
+    /// fn forecast(loc: LatLng) -> GenResult<()> {
+    ///     let report = get_weather(loc).log_err()?;
+    ///     println!("{:?}", report);
+    ///     Ok(())
+    /// }
+    /// ```
+    fn log_err(self) -> Self;
+}
+
+impl<T, E: Error> LogResultExt<T> for Result<T, E> {
+    fn log_err(self) -> Self {
+        if let Err(err) = &self {
+            for (i, cause) in chain(err).enumerate() {
+                if i == 0 {
+                    log::error!("error: {}", cause);
+                } else {
+                    log::error!("caused by: {}", cause);
+                }
+            }
+        }
+        self
+    }
+}
+
+// A logger that keeps every record, for tests to look at. The logger can
+// only be set once per process, so every test that checks log output shares
+// it, and picks out its own records by a marker in the message.
+#[cfg(test)]
+pub(crate) mod test_logger {
+    use log::{Level, Log, Metadata, Record};
+    use std::sync::Mutex;
+
+    struct CapturingLogger(Mutex<Vec<(Level, String)>>);
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            let mut records = self.0.lock().unwrap();
+            records.push((record.level(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger(Mutex::new(Vec::new()));
+
+    pub fn install() {
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Trace);
+    }
+
+    /// Every record logged so far whose message contains `marker`.
+    pub fn records_containing(marker: &str) -> Vec<(Level, String)> {
+        let records = LOGGER.0.lock().unwrap();
+        records
+            .iter()
+            .filter(|(_, message)| message.contains(marker))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_logger;
+    use super::*;
+    use crate::ext::ContextError;
+    use log::Level;
+    use std::io;
+
+    #[test]
+    fn logs_one_record_per_chain_level() {
+        test_logger::install();
+
+        let ok: io::Result<i32> = Ok(1);
+        assert_eq!(ok.log_err().unwrap(), 1);
+
+        let failed: Result<(), ContextError> = Err(ContextError {
+            context: "logging test: fetching weather".to_string(),
+            source: Box::new(io::Error::other("logging test: network outage")),
+        });
+        assert!(failed.log_err().is_err());
+
+        assert_eq!(
+            test_logger::records_containing("logging test"),
+            vec![
+                (
+                    Level::Error,
+                    "error: logging test: fetching weather".to_string()
+                ),
+                (
+                    Level::Error,
+                    "caused by: logging test: network outage".to_string()
+                ),
+            ]
+        );
+    }
+}
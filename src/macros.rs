@@ -0,0 +1,491 @@
+// Ad-hoc Errors
+
+// Making a one-off error with io::Error::new(io::ErrorKind::Other, "...")
+// is clunky, and claims to be an I/O error when it might not be. err! makes a
+// GenError from a formatted message, and bail! returns it. error_enum! writes
+// the boilerplate for an error enum that wraps other error types.
+
+/// Make a [`GenError`](crate::GenError) with a message built like
+/// `format!`.
+///
+/// ```
+/// use rust_error_result::err;
+///
+/// let ms = 500;
+/// let e = err!("timed out after {} ms", ms);
+/// assert_eq!(e.to_string(), "timed out after 500 ms");
+/// ```
+#[macro_export]
+macro_rules! err {
+    // A message with no braces needs no formatting, so it's kept as a
+    // borrowed &'static str rather than copied into a String.
+    ($msg:literal $(,)?) => {{
+        let msg: &'static str = $msg;
+        if msg.contains('{') || msg.contains('}') {
+            $crate::GenError::from($crate::MsgError::from(format!($msg)))
+        } else {
+            $crate::GenError::from($crate::MsgError::from_static(msg))
+        }
+    }};
+    ($($arg:tt)+) => {
+        $crate::GenError::from($crate::MsgError::from(format!($($arg)+)))
+    };
+}
+
+/// Return early with an [`err!`] error.
+///
+/// ```
+/// use rust_error_result::{bail, GenResult};
+///
+/// fn calculate_tides(planet: &str) -> GenResult<u32> {
+///     if planet == "mercury" {
+///         bail!("moon not found");
+///     }
+///     Ok(2)
+/// }
+///
+/// assert_eq!(calculate_tides("mercury").unwrap_err().to_string(), "moon not found");
+/// ```
+#[macro_export]
+macro_rules! bail {
+    ($($arg:tt)+) => {
+        return Err($crate::err!($($arg)+))
+    };
+}
+
+/// Return early with an [`err!`] error unless `cond` is true.
+///
+/// ```
+/// use rust_error_result::{ensure, GenResult, LatLng};
+///
+/// fn check(loc: LatLng) -> GenResult<()> {
+///     ensure!(loc.lat.abs() <= 90.0, "latitude out of range: {}", loc.lat);
+///     Ok(())
+/// }
+///
+/// let err = check(LatLng { lat: 91.0, lng: 0.0 }).unwrap_err();
+/// assert_eq!(err.to_string(), "latitude out of range: 91");
+/// ```
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            $crate::bail!($($arg)+);
+        }
+    };
+}
+
+/// Define an error enum where each variant wraps another error type.
+///
+/// Along with the enum, this generates a `From` impl for each wrapped type,
+/// so `?` converts them, and `Display` and `Error` impls that delegate to
+/// the wrapped error, as [`AppError`](crate::AppError) does. Like
+/// `AppError`, the enum is [`Transparent`](crate::transparent::Transparent):
+/// `source()` is the wrapped error's `source()`, so each message is printed
+/// once. To let [`downcast_root`](crate::downcast::downcast_root) and the
+/// like step into the wrapped error, call
+/// [`register_transparent`](crate::transparent::register_transparent) once.
+/// Attributes such as `#[derive(Debug)]` are passed through to the enum,
+/// which must derive `Debug` to implement `Error`.
+///
+/// Each wrapped type must be different, or the `From` impls conflict.
+///
+/// ```
+/// use rust_error_result::error_enum;
+/// use std::io;
+/// use std::num::ParseIntError;
+///
+/// error_enum! {
+///     #[derive(Debug)]
+///     pub enum TideError {
+///         Io(io::Error),
+///         Parse(ParseIntError),
+///     }
+/// }
+///
+/// fn high_tide(s: &str) -> Result<i64, TideError> {
+///     Ok(s.parse::<i64>()?)
+/// }
+///
+/// assert!(matches!(high_tide("bleen"), Err(TideError::Parse(_))));
+/// ```
+#[macro_export]
+macro_rules! error_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$vmeta:meta])*
+                $variant:ident($ty:ty)
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$vmeta])*
+                $variant($ty),
+            )+
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match self {
+                    $($name::$variant(err) => ::std::fmt::Display::fmt(err, f),)+
+                }
+            }
+        }
+
+        impl ::std::error::Error for $name {
+            fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                match self {
+                    $($name::$variant(err) => ::std::error::Error::source(err),)+
+                }
+            }
+        }
+
+        impl $crate::transparent::Transparent for $name {
+            fn wrapped(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                match self {
+                    $($name::$variant(err) => ::std::option::Option::Some(err),)+
+                }
+            }
+        }
+
+        $(
+            impl ::std::convert::From<$ty> for $name {
+                fn from(err: $ty) -> $name {
+                    $name::$variant(err)
+                }
+            }
+        )+
+    };
+}
+
+/// Unwrap a `Result` that should never fail, with different behavior in
+/// debug and release builds.
+///
+/// - In debug builds (with `debug_assertions`), it's like `.expect(msg)`:
+///   an `Err` panics, with `msg` and the error's whole chain of causes in
+///   the panic message, so a failure during development is loud.
+/// - In release builds, it's like `?`: an `Err` is returned from the
+///   enclosing function, so a failure in production is an error, not a
+///   crash.
+///
+/// Either way, `Ok(v)` evaluates to `v`. Since it may use `?`, the
+/// enclosing function must return a [`GenResult`](crate::GenResult), and
+/// the error must convert into a [`GenError`](crate::GenError).
+///
+/// ```
+/// use rust_error_result::{debug_expect, GenResult};
+///
+/// fn tide_height(s: &str) -> GenResult<i64> {
+///     Ok(debug_expect!(s.parse::<i64>(), "tide table is validated on load"))
+/// }
+///
+/// assert_eq!(tide_height("7").unwrap(), 7);
+/// ```
+#[macro_export]
+macro_rules! debug_expect {
+    ($expr:expr, $msg:expr) => {
+        match $expr {
+            Ok(value) => value,
+            Err(err) => {
+                let err: $crate::GenError = ::std::convert::Into::into(err);
+                if cfg!(debug_assertions) {
+                    panic!("{}: {}", $msg, $crate::ChainDisplay(&*err));
+                }
+                return Err(err);
+            }
+        }
+    };
+}
+
+/// Unwrap a `Result` inside a loop, or log the error and `continue`.
+///
+/// On `Ok(v)`, evaluates to `v`. On `Err(e)`, logs the message built from
+/// the remaining arguments and `e` with `log::error!`, then continues with
+/// the next iteration of the enclosing loop. Since it uses `continue`, it
+/// can only be used inside a loop.
+///
+/// ```
+/// use rust_error_result::try_continue;
+///
+/// let mut total = 0;
+/// for s in ["1", "bleen", "3"] {
+///     let n: i32 = try_continue!(s.parse(), "skipping {:?}", s);
+///     total += n;
+/// }
+/// assert_eq!(total, 4);
+/// ```
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! try_continue {
+    ($expr:expr, $($arg:tt)+) => {
+        match $expr {
+            Ok(value) => value,
+            Err(err) => {
+                $crate::__log::error!("{}: {}", format_args!($($arg)+), err);
+                continue;
+            }
+        }
+    };
+}
+
+/// Like [`try_continue!`], but `break` out of the loop on an error.
+///
+/// Since it uses `break`, it can only be used inside a loop.
+#[cfg(feature = "log")]
+#[macro_export]
+macro_rules! try_break {
+    ($expr:expr, $($arg:tt)+) => {
+        match $expr {
+            Ok(value) => value,
+            Err(err) => {
+                $crate::__log::error!("{}: {}", format_args!($($arg)+), err);
+                break;
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::GenResult;
+    use std::cell::Cell;
+
+    #[test]
+    fn err_formats_message() {
+        let ms = 250;
+        let e = err!("timed out after {} ms", ms);
+        assert_eq!(e.to_string(), "timed out after 250 ms");
+        assert!(e.source().is_none());
+    }
+
+    #[test]
+    fn bail_returns_early() {
+        fn check(n: i32) -> GenResult<i32> {
+            if n < 0 {
+                bail!("negative: {}", n);
+            }
+            Ok(n * 2)
+        }
+        assert_eq!(check(4).unwrap(), 8);
+        assert_eq!(check(-1).unwrap_err().to_string(), "negative: -1");
+    }
+
+    #[test]
+    fn ensure_checks_condition_once() {
+        fn check(evaluations: &Cell<u32>, n: i32) -> GenResult<i32> {
+            ensure!(
+                {
+                    evaluations.set(evaluations.get() + 1);
+                    n >= 0
+                },
+                "negative: {}",
+                n
+            );
+            Ok(n)
+        }
+
+        let evaluations = Cell::new(0);
+        assert_eq!(check(&evaluations, 3).unwrap(), 3);
+        assert_eq!(evaluations.get(), 1);
+
+        let err = check(&evaluations, -3).unwrap_err();
+        assert_eq!(err.to_string(), "negative: -3");
+        assert_eq!(evaluations.get(), 2);
+    }
+
+    fn debug_expect_parse(s: &str) -> GenResult<i64> {
+        Ok(debug_expect!(s.parse::<i64>(), "tide table was validated"))
+    }
+
+    #[test]
+    fn debug_expect_unwraps_ok() {
+        assert_eq!(debug_expect_parse("7").unwrap(), 7);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "tide table was validated: invalid digit found in string")]
+    fn debug_expect_panics_in_debug() {
+        let _ = debug_expect_parse("bleen");
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn debug_expect_propagates_in_release() {
+        let err = debug_expect_parse("bleen").unwrap_err();
+        assert_eq!(err.to_string(), "invalid digit found in string");
+    }
+
+    mod generated {
+        use std::io;
+        use std::num::ParseIntError;
+
+        error_enum! {
+            /// Like `AppError`, without `Other`.
+            #[derive(Debug)]
+            pub enum SmallError {
+                Io(io::Error),
+                Parse(ParseIntError),
+            }
+        }
+
+        error_enum! {
+            /// Variants whose wrapped errors have sources of their own.
+            #[derive(Debug)]
+            pub enum ReadError {
+                Line(crate::numbers::LineParseError),
+                Context(crate::ext::ContextError),
+                Timeout(crate::timeout::TimeoutError),
+            }
+        }
+    }
+
+    #[test]
+    fn error_enum_matches_hand_written() {
+        use self::generated::SmallError;
+        use crate::apperror::AppError;
+        use crate::numbers::LineParseError;
+        use std::error::Error;
+        use std::io;
+
+        fn parse(s: &str) -> Result<i64, SmallError> {
+            Ok(s.parse::<i64>()?)
+        }
+        fn fail_io() -> Result<(), SmallError> {
+            let source = "bleen".parse::<i64>().unwrap_err();
+            Err(io::Error::other(LineParseError { line: 2, source }))?
+        }
+
+        let parse_err = parse("bleen").unwrap_err();
+        assert!(matches!(parse_err, SmallError::Parse(_)));
+        assert_eq!(parse_err.to_string(), "invalid digit found in string");
+        assert!(parse_err.source().is_none());
+
+        let io_err = fail_io().unwrap_err();
+        assert!(matches!(io_err, SmallError::Io(_)));
+        let source = io_err.source().unwrap();
+        assert_eq!(source.to_string(), "invalid digit found in string");
+
+        // The same errors, wrapped in the hand-written AppError.
+        let app_err = AppError::from(io::Error::other(LineParseError {
+            line: 2,
+            source: "bleen".parse::<i64>().unwrap_err(),
+        }));
+        assert_eq!(app_err.to_string(), io_err.to_string());
+        assert_eq!(app_err.source().unwrap().to_string(), source.to_string());
+    }
+
+    #[test]
+    fn error_enum_source_is_wrapped_errors_source() {
+        use self::generated::ReadError;
+        use crate::ext::ResultExt;
+        use crate::numbers::LineParseError;
+        use crate::timeout::TimeoutError;
+        use std::error::Error;
+        use std::io;
+        use std::num::ParseIntError;
+        use std::time::Duration;
+
+        fn parse_line(s: &str) -> Result<i64, ReadError> {
+            let n = s
+                .parse::<i64>()
+                .map_err(|source| LineParseError { line: 4, source })?;
+            Ok(n)
+        }
+        fn open() -> Result<(), ReadError> {
+            let missing: io::Result<()> = Err(io::ErrorKind::NotFound.into());
+            let err = missing.context("opening tide table").unwrap_err();
+            Err(*err.downcast::<crate::ext::ContextError>().unwrap())?
+        }
+
+        assert_eq!(parse_line("7").unwrap(), 7);
+        let err = parse_line("bleen").unwrap_err();
+        assert!(matches!(err, ReadError::Line(_)));
+        assert_eq!(
+            err.to_string(),
+            "parse error on line 4: invalid digit found in string"
+        );
+        assert!(err.source().unwrap().is::<ParseIntError>());
+
+        let err = open().unwrap_err();
+        assert_eq!(err.to_string(), "opening tide table");
+        let io_err = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+
+        let err = ReadError::from(TimeoutError {
+            waited: Duration::from_secs(5),
+        });
+        assert_eq!(err.to_string(), "operation timed out after 5 seconds");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn error_enum_chain_reaches_variant_error() {
+        use self::generated::ReadError;
+        use crate::numbers::LineParseError;
+        use crate::timeout::TimeoutError;
+        use std::time::Duration;
+
+        crate::transparent::register_transparent::<ReadError>();
+
+        let source = "bleen".parse::<i64>().unwrap_err();
+        let err = ReadError::from(LineParseError { line: 4, source });
+        let line_err = crate::chain::static_chain(&err)
+            .find_map(|e| e.downcast_ref::<LineParseError>())
+            .unwrap();
+        assert_eq!(line_err.line, 4);
+
+        let err = ReadError::Timeout(TimeoutError {
+            waited: Duration::from_secs(5),
+        });
+        assert!(crate::chain_contains::<TimeoutError>(&err));
+        assert_eq!(crate::categorize(&err), crate::ErrorCategory::Timeout);
+
+        let mut out = Vec::new();
+        crate::write_error(&mut out, &err).unwrap();
+        assert_eq!(out, b"error: operation timed out after 5 seconds\n");
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn try_continue_skips_failures() {
+        crate::logging::test_logger::install();
+        let inputs = ["1", "bleen", "3", "-"];
+        let mut parsed = vec![];
+        for (i, s) in inputs.iter().enumerate() {
+            let n: i32 = try_continue!(s.parse(), "try_continue test: item {}", i);
+            parsed.push(n);
+        }
+        assert_eq!(parsed, vec![1, 3]);
+
+        let records = crate::logging::test_logger::records_containing("try_continue test");
+        let messages: Vec<&str> = records.iter().map(|(_, m)| m.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "try_continue test: item 1: invalid digit found in string",
+                "try_continue test: item 3: invalid digit found in string",
+            ]
+        );
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn try_break_stops_at_failure() {
+        crate::logging::test_logger::install();
+        let mut parsed = vec![];
+        for s in ["1", "2", "bleen", "4"] {
+            let n: i32 = try_break!(s.parse(), "try_break test: {:?}", s);
+            parsed.push(n);
+        }
+        assert_eq!(parsed, vec![1, 2]);
+        assert_eq!(
+            crate::logging::test_logger::records_containing("try_break test").len(),
+            1
+        );
+    }
+}
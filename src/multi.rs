@@ -0,0 +1,212 @@
+// Multiple Causes
+
+// Error::source() gives each error at most one cause, so errors form a
+// chain. Sometimes an operation fails for several independent reasons at
+// once, like a form with more than one invalid field. MultiError keeps all of
+// them, making the chain into a tree. combine() is for when handling one
+// error causes another, and we don't want to lose either. run_all() runs
+// several independent tasks and reports every one that failed.
+
+use crate::ext::ContextError;
+use crate::format::ChainDisplay;
+use crate::prelude::{GenError, GenResult};
+use std::error::Error;
+use std::fmt;
+
+/// An error with any number of causes.
+///
+/// Displays the message, then each cause with its own causes on an indented
+/// line. `source()` can only return one error, so it's the first cause; use
+/// [`causes`](MultiError::causes) to see them all.
+#[derive(Debug)]
+pub struct MultiError {
+    message: String,
+    causes: Vec<GenError>,
+}
+
+impl MultiError {
+    pub fn new<S: Into<String>>(message: S, causes: Vec<GenError>) -> MultiError {
+        MultiError {
+            message: message.into(),
+            causes,
+        }
+    }
+
+    /// The message alone, without the causes.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn causes(&self) -> &[GenError] {
+        &self.causes
+    }
+}
+
+impl fmt::Display for MultiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for cause in &self.causes {
+            write!(f, "\n  - {}", ChainDisplay(&**cause))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for MultiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.causes
+            .first()
+            .map(|cause| &**cause as &(dyn Error + 'static))
+    }
+}
+
+/// One error holding both `a` and `b`.
+///
+/// If `a` is a [`MultiError`], `b` is added to its causes; otherwise if `b`
+/// is one, `a` is added to the front of its causes. Only if neither is, a
+/// new `MultiError` is made, with the message "multiple errors".
+///
+/// ```
+/// use rust_error_result::{combine, err};
+///
+/// let both = combine(err!("rollback failed"), err!("disk full"));
+/// assert_eq!(both.to_string(), "multiple errors\n  - rollback failed\n  - disk full");
+/// ```
+pub fn combine(a: GenError, b: GenError) -> GenError {
+    let a = match a.downcast::<MultiError>() {
+        Ok(mut multi) => {
+            multi.causes.push(b);
+            return multi;
+        }
+        Err(a) => a,
+    };
+    match b.downcast::<MultiError>() {
+        Ok(mut multi) => {
+            multi.causes.insert(0, a);
+            multi
+        }
+        Err(b) => Box::new(MultiError::new("multiple errors", vec![a, b])),
+    }
+}
+
+/// A task for [`run_all`].
+pub type Task<'a> = Box<dyn FnOnce() -> GenResult<()> + 'a>;
+
+/// Run every task, even after one fails. If any fail, return a
+/// [`MultiError`] with each failure, in order, wrapped in a
+/// [`ContextError`] giving the task's name.
+pub fn run_all(tasks: Vec<(&str, Task)>) -> Result<(), MultiError> {
+    let total = tasks.len();
+    let mut failures = vec![];
+    for (name, task) in tasks {
+        if let Err(source) = task() {
+            failures.push(GenError::from(ContextError {
+                context: name.to_string(),
+                source,
+            }));
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        let message = format!("{} of {} tasks failed", failures.len(), total);
+        Err(MultiError::new(message, failures))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_form() -> MultiError {
+        MultiError::new(
+            "invalid form",
+            vec![
+                crate::err!("name is empty"),
+                crate::err!("age is negative"),
+                crate::err!("email has no @"),
+            ],
+        )
+    }
+
+    #[test]
+    fn displays_every_cause() {
+        assert_eq!(
+            invalid_form().to_string(),
+            "invalid form\n  \
+             - name is empty\n  \
+             - age is negative\n  \
+             - email has no @"
+        );
+    }
+
+    #[test]
+    fn source_is_first_cause() {
+        let err = invalid_form();
+        assert_eq!(err.source().unwrap().to_string(), "name is empty");
+        assert_eq!(err.causes().len(), 3);
+
+        let empty = MultiError::new("nothing wrong", vec![]);
+        assert!(empty.source().is_none());
+        assert_eq!(empty.to_string(), "nothing wrong");
+    }
+
+    fn messages(err: &GenError) -> Vec<String> {
+        let multi = err.downcast_ref::<MultiError>().unwrap();
+        multi.causes().iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn combine_two_plain_errors() {
+        let both = combine(crate::err!("rollback failed"), crate::err!("disk full"));
+        assert_eq!(messages(&both), ["rollback failed", "disk full"]);
+        assert_eq!(both.source().unwrap().to_string(), "rollback failed");
+    }
+
+    #[test]
+    fn combine_appends_to_existing_multi_error() {
+        let first = combine(crate::err!("rollback failed"), crate::err!("disk full"));
+        let more = combine(first, crate::err!("lock not released"));
+        assert_eq!(
+            messages(&more),
+            ["rollback failed", "disk full", "lock not released"]
+        );
+
+        let front = combine(crate::err!("commit failed"), more);
+        assert_eq!(messages(&front).len(), 4);
+        assert_eq!(messages(&front)[0], "commit failed");
+        assert!(front.to_string().starts_with("multiple errors\n"));
+    }
+
+    #[test]
+    fn run_all_reports_every_failure() {
+        let ran = std::cell::Cell::new(0);
+        let err = run_all(vec![
+            (
+                "open database",
+                Box::new(|| Err(crate::err!("connection refused"))),
+            ),
+            (
+                "load config",
+                Box::new(|| {
+                    ran.set(ran.get() + 1);
+                    Ok(())
+                }),
+            ),
+            ("warm cache", Box::new(|| Err(crate::err!("disk full")))),
+        ])
+        .unwrap_err();
+
+        assert_eq!(ran.get(), 1);
+        assert_eq!(
+            err.to_string(),
+            "2 of 3 tasks failed\n  \
+             - open database: connection refused\n  \
+             - warm cache: disk full"
+        );
+        let names: Vec<String> = err.causes().iter().map(|c| c.to_string()).collect();
+        assert_eq!(names, ["open database", "warm cache"]);
+
+        assert!(run_all(vec![("noop", Box::new(|| Ok(())))]).is_ok());
+    }
+}
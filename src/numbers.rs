@@ -0,0 +1,332 @@
+// Reading Numbers
+
+// Reading a line from a file and parsing an integer produce two different
+// error types: io::Error and std::num::ParseIntError. Returning a GenResult
+// lets the ? operator convert either one into a GenError. The same goes for
+// any other type parsed with FromStr, so read_numbers_as() can read numbers
+// of any type.
+
+use crate::collect::ErrorCollection;
+use crate::ext::GenResultExt;
+use crate::io_convert::with_path;
+use crate::prelude::GenResult;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::num::ParseIntError;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A line of a file that didn't parse as a number.
+///
+/// `E` is the error from parsing the line, a `ParseIntError` unless
+/// [`read_numbers_as`] was reading some other type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineParseError<E = ParseIntError> {
+    /// The line number, starting from 1.
+    pub line: usize,
+    pub source: E,
+}
+
+impl<E: fmt::Display> fmt::Display for LineParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "parse error on line {}: {}", self.line, self.source)
+    }
+}
+
+impl<E: Error + 'static> Error for LineParseError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Read integers from a text file.
+/// The file should have one number on each line.
+///
+/// Surrounding whitespace is trimmed before parsing, and blank lines are
+/// skipped. A line that doesn't parse is reported as a [`LineParseError`].
+pub fn read_numbers<R: BufRead>(file: &mut R) -> GenResult<Vec<i64>> {
+    read_numbers_as::<i64, _>(file)
+}
+
+/// Like [`read_numbers`], but for numbers of any type `T`, or anything else
+/// that can be parsed with `str::parse`.
+///
+/// A line that doesn't parse is reported as a [`LineParseError`] whose
+/// source is `T`'s parse error.
+///
+/// ```
+/// use rust_error_result::read_numbers_as;
+/// use std::io::Cursor;
+///
+/// let heights: Vec<f32> = read_numbers_as(&mut Cursor::new("1.5\n-0.25\n")).unwrap();
+/// assert_eq!(heights, vec![1.5, -0.25]);
+/// ```
+pub fn read_numbers_as<T, R>(file: &mut R) -> GenResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+    R: BufRead,
+{
+    let mut numbers = vec![];
+    for (i, line_result) in file.lines().enumerate() {
+        let line = line_result?; // reading lines can fail
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        // parsing integers can fail
+        let n = line.parse().map_err(|source| LineParseError {
+            line: i + 1,
+            source,
+        })?;
+        numbers.push(n);
+    }
+    Ok(numbers)
+}
+
+/// Open the file at `path` and read integers from it with
+/// [`read_numbers`].
+///
+/// An error opening the file names the path, as [`with_path`] does. An
+/// error reading or parsing it is given the context "reading numbers from
+/// {path}".
+pub fn read_numbers_from_path(path: &Path) -> GenResult<Vec<i64>> {
+    let file = with_path(File::open(path), path)?;
+    read_numbers(&mut BufReader::new(file))
+        .with_context(|| format!("reading numbers from {}", path.display()))
+}
+
+/// Integers read lazily from a text file, one per line, like
+/// [`read_numbers`].
+///
+/// Each item is a number, or the error for that line: an `io::Error`, or a
+/// [`LineParseError`]. Nothing past a line is read until it is needed, so
+/// stopping at the first error leaves the rest of the file unread.
+///
+/// ```
+/// use rust_error_result::numbers::NumberLines;
+/// use rust_error_result::GenResult;
+/// use std::io::Cursor;
+///
+/// fn sum(file: Cursor<&str>) -> GenResult<i64> {
+///     let mut total = 0;
+///     for n in NumberLines::new(file) {
+///         total += n?;
+///     }
+///     Ok(total)
+/// }
+///
+/// assert_eq!(sum(Cursor::new("1\n2\n3\n")).unwrap(), 6);
+/// assert!(sum(Cursor::new("1\nbleen\n3\n")).is_err());
+/// ```
+#[derive(Debug)]
+pub struct NumberLines<R: BufRead> {
+    inner: Lines<R>,
+    line_no: usize,
+}
+
+impl<R: BufRead> NumberLines<R> {
+    pub fn new(file: R) -> NumberLines<R> {
+        NumberLines {
+            inner: file.lines(),
+            line_no: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for NumberLines<R> {
+    type Item = GenResult<i64>;
+
+    fn next(&mut self) -> Option<GenResult<i64>> {
+        loop {
+            let line = match self.inner.next()? {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err.into())),
+            };
+            self.line_no += 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line_no = self.line_no;
+            return Some(line.parse().map_err(|source| {
+                LineParseError {
+                    line: line_no,
+                    source,
+                }
+                .into()
+            }));
+        }
+    }
+}
+
+/// Like [`read_numbers`], but keep going past lines that don't parse, and
+/// report all of them, each as a [`LineParseError`].
+///
+/// An error reading the file still stops everything right away.
+pub fn read_numbers_all<R: BufRead>(file: &mut R) -> GenResult<Vec<i64>> {
+    let mut numbers = vec![];
+    let mut errors = ErrorCollection::new();
+    for (i, line_result) in file.lines().enumerate() {
+        let line = line_result?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match line.parse() {
+            Ok(n) => numbers.push(n),
+            Err(source) => errors.push(Box::new(LineParseError {
+                line: i + 1,
+                source,
+            })),
+        }
+    }
+    errors.into_result(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, Cursor};
+    use std::{env, fs, process};
+
+    #[test]
+    fn reads_good_file() {
+        let mut file = Cursor::new("1\n  -2 \n\n30\r\n");
+        assert_eq!(read_numbers(&mut file).unwrap(), vec![1, -2, 30]);
+    }
+
+    #[test]
+    fn non_numeric_line_is_a_parse_error() {
+        let mut file = Cursor::new("1\n\nbleen\n4\n");
+        let err = read_numbers(&mut file).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse error on line 3: invalid digit found in string"
+        );
+
+        let line_err = err.downcast_ref::<LineParseError>().unwrap();
+        assert_eq!(line_err.line, 3);
+        let parse_err = err.source().unwrap().downcast_ref::<ParseIntError>();
+        assert_eq!(parse_err, Some(&line_err.source));
+    }
+
+    #[test]
+    fn overflowing_integer_is_a_parse_error() {
+        let mut file = Cursor::new("99999999999999999999999999999999\n");
+        let err = read_numbers(&mut file).unwrap_err();
+        let line_err = err.downcast_ref::<LineParseError>().unwrap();
+        assert_eq!(line_err.line, 1);
+        assert_eq!(
+            line_err.source.to_string(),
+            "number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn number_lines_stops_at_first_bad_line() {
+        let mut file = Cursor::new("1\n\n2\nbleen\n3\n");
+        let mut numbers = vec![];
+        let mut error = None;
+        for n in NumberLines::new(&mut file) {
+            match n {
+                Ok(n) => numbers.push(n),
+                Err(err) => {
+                    error = Some(err);
+                    break;
+                }
+            }
+        }
+
+        assert_eq!(numbers, vec![1, 2]);
+        let err = error.unwrap();
+        let lpe = err.downcast_ref::<LineParseError>().unwrap();
+        assert_eq!(lpe.line, 4);
+        // The last line was never read.
+        assert_eq!(file.position(), "1\n\n2\nbleen\n".len() as u64);
+    }
+
+    #[test]
+    fn number_lines_yields_io_errors() {
+        let mut lines = NumberLines::new(Cursor::new(vec![b'7', b'\n', 0xff, b'\n']));
+        assert_eq!(lines.next().unwrap().unwrap(), 7);
+        let err = lines.next().unwrap().unwrap_err();
+        assert!(err.downcast_ref::<std::io::Error>().is_some());
+    }
+
+    #[test]
+    fn read_from_path() {
+        let path = env::temp_dir().join(format!("read_numbers_test_{}.txt", process::id()));
+        fs::write(&path, "1\n2\n\n3\n").unwrap();
+        let numbers = read_numbers_from_path(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(numbers.unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn bad_line_in_path_has_context() {
+        let path = env::temp_dir().join(format!("read_numbers_bad_{}.txt", process::id()));
+        fs::write(&path, "1\nbleen\n").unwrap();
+        let err = read_numbers_from_path(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!("reading numbers from {}", path.display())
+        );
+        assert_eq!(
+            err.source().unwrap().to_string(),
+            "parse error on line 2: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn missing_path_has_context() {
+        let path = Path::new("/no/such/dir/tides.txt");
+        let err = read_numbers_from_path(path).unwrap_err();
+        // The OS's message varies by platform, so only check that the path
+        // is named, and named once.
+        let path_str = path.display().to_string();
+        assert_eq!(err.to_string().matches(path_str.as_str()).count(), 1);
+        let io_err = crate::downcast::downcast_root::<io::Error>(&*err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn read_as_u32() {
+        let mut file = Cursor::new("0\n4294967295\n");
+        let numbers: Vec<u32> = read_numbers_as(&mut file).unwrap();
+        assert_eq!(numbers, vec![0, u32::MAX]);
+
+        let mut file = Cursor::new("1\n4294967296\n");
+        let err = read_numbers_as::<u32, _>(&mut file).unwrap_err();
+        let lpe = err.downcast_ref::<LineParseError>().unwrap();
+        assert_eq!(lpe.line, 2);
+        assert_eq!(lpe.source.kind(), &std::num::IntErrorKind::PosOverflow);
+
+        let mut file = Cursor::new("-1\n");
+        assert!(read_numbers_as::<u32, _>(&mut file).is_err());
+    }
+
+    #[test]
+    fn read_as_i128() {
+        let mut file = Cursor::new("170141183460469231731687303715884105727\n-5\n");
+        let numbers: Vec<i128> = read_numbers_as(&mut file).unwrap();
+        assert_eq!(numbers, vec![i128::MAX, -5]);
+    }
+
+    #[test]
+    fn read_as_other_types_keeps_their_parse_error() {
+        let mut file = Cursor::new("1.5\nhigh\n");
+        let err = read_numbers_as::<f64, _>(&mut file).unwrap_err();
+        let lpe = err
+            .downcast_ref::<LineParseError<std::num::ParseFloatError>>()
+            .unwrap();
+        assert_eq!(lpe.line, 2);
+        assert_eq!(
+            err.to_string(),
+            "parse error on line 2: invalid float literal"
+        );
+    }
+}
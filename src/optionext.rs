@@ -0,0 +1,73 @@
+// From Option to Result
+
+// result.ok() turns a Result into an Option, discarding the error. Going the
+// other way, a None needs an error made up for it. or_err() makes one from a
+// message.
+
+use crate::prelude::GenResult;
+use crate::string_error::MsgError;
+use std::fmt::Display;
+
+pub trait OptionExt<T> {
+    /// `Ok(v)` for `Some(v)`; for `None`, an error with the message `ctx`.
+    ///
+    /// ```
+    /// use rust_error_result::optionext::OptionExt;
+    /// use std::collections::HashMap;
+    ///
+    /// let config: HashMap<&str, &str> = HashMap::new();
+    /// let err = config.get("host").or_err("missing host").unwrap_err();
+    /// assert_eq!(err.to_string(), "missing host");
+    /// ```
+    fn or_err<C: Display>(self, ctx: C) -> GenResult<T>;
+
+    /// Like [`or_err`](OptionExt::or_err), but `f` builds the message only
+    /// if it's needed.
+    fn or_err_with<C, F>(self, f: F) -> GenResult<T>
+    where
+        C: Display,
+        F: FnOnce() -> C;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn or_err<C: Display>(self, ctx: C) -> GenResult<T> {
+        self.ok_or_else(|| MsgError::from(ctx.to_string()).into())
+    }
+
+    fn or_err_with<C, F>(self, f: F) -> GenResult<T>
+    where
+        C: Display,
+        F: FnOnce() -> C,
+    {
+        self.ok_or_else(|| MsgError::from(f().to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn some_and_none() {
+        assert_eq!(Some(80).or_err("missing port").unwrap(), 80);
+        let err = None::<u16>.or_err("missing port").unwrap_err();
+        assert_eq!(err.to_string(), "missing port");
+    }
+
+    #[test]
+    fn or_err_with_is_lazy() {
+        let calls = Cell::new(0);
+        let message = || {
+            calls.set(calls.get() + 1);
+            format!("missing {}", "host")
+        };
+
+        assert_eq!(Some("localhost").or_err_with(message).unwrap(), "localhost");
+        assert_eq!(calls.get(), 0);
+
+        let err = None::<&str>.or_err_with(message).unwrap_err();
+        assert_eq!(err.to_string(), "missing host");
+        assert_eq!(calls.get(), 1);
+    }
+}
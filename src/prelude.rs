@@ -0,0 +1,36 @@
+// Working with Multiple Error Types
+
+// All of the standard library error types can be converted to the type
+// Box<dyn std::error::Error>, which represents "any error". The aliases here
+// also require Send + Sync + 'static, so a GenError can be moved across
+// threads and returned from a spawned thread's closure.
+
+/// Any error, boxed.
+pub type GenError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A `Result` whose error can be any [`GenError`].
+///
+/// The `?` operator converts each error type into a `GenError` as needed:
+///
+/// ```
+/// use rust_error_result::prelude::*;
+/// use std::io::{self, BufRead};
+///
+/// /// Read integers from a text file.
+/// /// The file should have one number on each line.
+/// fn read_numbers(file: &mut dyn BufRead) -> GenResult<Vec<i64>> {
+///     let mut numbers = vec![];
+///     for line_result in file.lines() {
+///         let line = line_result?; // reading lines can fail
+///         numbers.push(line.parse()?); // parsing integers can fail
+///     }
+///     Ok(numbers)
+/// }
+///
+/// let mut input = io::Cursor::new("1\n2\n3\n");
+/// assert_eq!(read_numbers(&mut input).unwrap(), vec![1, 2, 3]);
+///
+/// let mut bad = io::Cursor::new("1\nbleen\n");
+/// assert!(read_numbers(&mut bad).is_err());
+/// ```
+pub type GenResult<T> = Result<T, GenError>;
@@ -0,0 +1,103 @@
+// Redacting Errors
+
+// An error from a library can carry anything in its message, including a
+// password or an API token that must not reach the logs. RedactedError masks
+// given strings everywhere in an error's chain.
+
+use crate::format::ChainDisplay;
+use crate::prelude::GenError;
+use std::error::Error;
+use std::fmt;
+
+/// An error with secret strings masked out of its messages.
+///
+/// Displays the whole chain on one line, like
+/// [`ChainDisplay`], with every occurrence of each pattern replaced by
+/// "***". `source()` is `None`: the causes are already in the message, and
+/// exposing them unredacted would defeat the purpose. For the same reason,
+/// `Debug` shows only the redacted message, and not the patterns.
+pub struct RedactedError {
+    inner: GenError,
+    patterns: Vec<String>,
+}
+
+impl RedactedError {
+    pub fn new<E: Into<GenError>>(err: E, patterns: Vec<String>) -> RedactedError {
+        RedactedError {
+            inner: err.into(),
+            patterns,
+        }
+    }
+
+    /// The original error, secrets and all.
+    pub fn into_inner(self) -> GenError {
+        self.inner
+    }
+}
+
+impl fmt::Display for RedactedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut message = ChainDisplay(&*self.inner).to_string();
+        for pattern in &self.patterns {
+            if !pattern.is_empty() {
+                message = message.replace(pattern.as_str(), "***");
+            }
+        }
+        f.write_str(&message)
+    }
+}
+
+impl fmt::Debug for RedactedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RedactedError")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
+impl Error for RedactedError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::ext::{ContextError, ResultExt};
+    use std::io;
+
+    #[test]
+    fn secrets_are_masked_at_every_level() {
+        let token = "tok_12345".to_string();
+        let failed = Err::<(), _>(ContextError {
+            context: format!("GET /weather?token={}", token),
+            source: Box::new(io::Error::other(format!("bad token {}", token))),
+        });
+        let err = failed
+            .redact(vec![token, "hunter2".to_string()])
+            .unwrap_err();
+
+        let message = err.to_string();
+        assert_eq!(message, "GET /weather?token=***: bad token ***");
+        assert!(!message.contains("tok_12345"));
+        assert!(std::error::Error::source(&err).is_none());
+    }
+
+    #[test]
+    fn debug_hides_secrets() {
+        let failed: io::Result<()> = Err(io::Error::other("bad password hunter2"));
+        let err = failed
+            .redact(vec!["hunter2".to_string(), "tok_12345".to_string()])
+            .unwrap_err();
+
+        let debug = format!("{:?}", err);
+        assert_eq!(debug, r#"RedactedError("bad password ***")"#);
+        assert!(!debug.contains("hunter2"));
+        assert!(!debug.contains("tok_12345"));
+    }
+
+    #[test]
+    fn other_text_is_untouched() {
+        let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+        let err = failed
+            .redact(vec!["hunter2".to_string(), String::new()])
+            .unwrap_err();
+        assert_eq!(err.to_string(), "moon not found");
+    }
+}
@@ -0,0 +1,120 @@
+// Error IDs
+
+// Dashboards and alerts want a stable name for each kind of error, one that
+// doesn't change when a message is reworded. ErrorRegistry maps error types
+// to IDs, and looks up the ID of the first error in a chain that has one.
+
+use crate::chain::static_chain;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// Maps error types to stable string IDs.
+///
+/// ```
+/// use rust_error_result::registry::ErrorRegistry;
+/// use rust_error_result::ext::ResultExt;
+/// use std::io;
+///
+/// let mut registry = ErrorRegistry::new();
+/// registry.register::<io::Error>("io");
+///
+/// let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+/// let err = failed.context("calculating tides").unwrap_err();
+/// assert_eq!(registry.id_of(&*err), Some("io"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ErrorRegistry {
+    map: HashMap<TypeId, &'static str>,
+    // One check per registered type, in the order they were registered. A
+    // `dyn Error` can't report its own TypeId, only say whether it is some
+    // given type.
+    checks: Vec<(TypeId, TypeCheck)>,
+}
+
+type TypeCheck = fn(&(dyn Error + 'static)) -> bool;
+
+impl ErrorRegistry {
+    pub fn new() -> ErrorRegistry {
+        ErrorRegistry::default()
+    }
+
+    /// Give errors of type `E` the ID `id`, replacing any earlier ID.
+    pub fn register<E: Error + 'static>(&mut self, id: &'static str) {
+        let type_id = TypeId::of::<E>();
+        if self.map.insert(type_id, id).is_none() {
+            self.checks.push((type_id, is_type::<E>));
+        }
+    }
+
+    /// The ID of the first error in `err`'s chain whose type is registered.
+    ///
+    /// Only errors with no borrowed data have a `TypeId`, so `err` must be
+    /// `'static`.
+    pub fn id_of(&self, err: &(dyn Error + 'static)) -> Option<&'static str> {
+        static_chain(err).find_map(|cause| {
+            self.checks
+                .iter()
+                .find(|(_, is)| is(cause))
+                .map(|(type_id, _)| self.map[type_id])
+        })
+    }
+}
+
+fn is_type<E: Error + 'static>(err: &(dyn Error + 'static)) -> bool {
+    err.is::<E>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::{ContextError, ResultExt};
+    use std::io;
+    use std::num::ParseIntError;
+
+    fn registry() -> ErrorRegistry {
+        let mut registry = ErrorRegistry::new();
+        registry.register::<io::Error>("io");
+        registry.register::<ParseIntError>("parse_int");
+        registry
+    }
+
+    #[test]
+    fn finds_registered_types_in_chain() {
+        let registry = registry();
+
+        let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+        let err = failed.context("calculating tides").unwrap_err();
+        assert_eq!(registry.id_of(&*err), Some("io"));
+
+        let parsed = "bleen".parse::<u64>().context("reading tide height");
+        let err = ContextError {
+            context: "calculating tides".to_string(),
+            source: parsed.unwrap_err(),
+        };
+        assert_eq!(registry.id_of(&err), Some("parse_int"));
+
+        let bare = "bleen".parse::<u64>().unwrap_err();
+        assert_eq!(registry.id_of(&bare), Some("parse_int"));
+    }
+
+    #[test]
+    fn unregistered_chain_has_no_id() {
+        let registry = registry();
+        let err = crate::err!("moon not found");
+        assert_eq!(registry.id_of(&*err), None);
+        assert_eq!(ErrorRegistry::new().id_of(&io::Error::other("x")), None);
+    }
+
+    #[test]
+    fn outermost_registered_error_wins() {
+        let mut registry = registry();
+        registry.register::<ContextError>("context");
+        registry.register::<io::Error>("io_v2");
+
+        let failed: io::Result<()> = Err(io::Error::other("moon not found"));
+        let err = failed.context("calculating tides").unwrap_err();
+        assert_eq!(registry.id_of(&*err), Some("context"));
+        assert_eq!(registry.id_of(&io::Error::other("x")), Some("io_v2"));
+    }
+}
@@ -0,0 +1,71 @@
+// Error Reports
+
+// At the end of a long run, we want a summary of everything that went wrong.
+// report_errors() numbers the errors, and puts each one's whole chain of
+// causes on its own line.
+
+use crate::format::ChainDisplay;
+use crate::prelude::GenError;
+use std::fmt::Write;
+
+/// A numbered list of `errors`, with a heading that counts them.
+///
+/// ```
+/// use rust_error_result::{err, report_errors};
+///
+/// let errors = vec![err!("moon not found"), err!("tide table missing")];
+/// assert_eq!(
+///     report_errors(&errors),
+///     "2 errors:\n 1. moon not found\n 2. tide table missing"
+/// );
+/// ```
+pub fn report_errors(errors: &[GenError]) -> String {
+    let mut report = match errors.len() {
+        0 => return "no errors".to_string(),
+        1 => "1 error:".to_string(),
+        n => format!("{} errors:", n),
+    };
+    for (i, err) in errors.iter().enumerate() {
+        // Writing to a String can't fail.
+        let _ = write!(report, "\n {}. {}", i + 1, ChainDisplay(&**err));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ResultExt;
+    use std::io;
+
+    #[test]
+    fn no_errors() {
+        assert_eq!(report_errors(&[]), "no errors");
+    }
+
+    #[test]
+    fn one_error_is_singular() {
+        let failed: io::Result<()> = Err(io::Error::other("network outage"));
+        let errors = vec![failed.context("fetching weather").unwrap_err()];
+        assert_eq!(
+            report_errors(&errors),
+            "1 error:\n 1. fetching weather: network outage"
+        );
+    }
+
+    #[test]
+    fn three_errors_are_numbered() {
+        let errors = vec![
+            crate::err!("moon not found"),
+            "bleen".parse::<i64>().context("line 2").unwrap_err(),
+            crate::err!("tide table missing"),
+        ];
+        assert_eq!(
+            report_errors(&errors),
+            "3 errors:\n \
+             1. moon not found\n \
+             2. line 2: invalid digit found in string\n \
+             3. tide table missing"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+// Retrying
+
+// Some errors are worth another try, like a network outage while querying
+// the weather. retry() runs an operation again until it succeeds or we run
+// out of attempts. retry_with_backoff() also waits between attempts, and
+// gives up right away on errors that aren't transient. retry_within() limits
+// the total time spent instead of the number of attempts. The time is kept
+// by a Clock, so tests can check it without actually waiting. When any of
+// them gives up, the last error gets the context "failed after N attempts",
+// so the log shows how hard we tried.
+
+use crate::classify::Transient;
+use crate::clock::{Clock, SystemClock};
+use crate::ext::ResultExt;
+use crate::prelude::GenResult;
+use std::error::Error;
+use std::time::Duration;
+
+/// Run `op` up to `attempts` times, returning the first `Ok` or the last
+/// `Err`, with the context "failed after N attempts".
+///
+/// `op` always runs at least once, even if `attempts` is 0.
+///
+/// ```
+/// use rust_error_result::retry::retry;
+/// use rust_error_result::{get_weather, LatLng};
+///
+/// let hometown = LatLng { lat: 34.05, lng: -118.24 };
+/// let report = retry(3, || get_weather(hometown));
+/// assert!(report.is_ok());
+/// ```
+pub fn retry<T, E, F>(attempts: usize, op: F) -> GenResult<T>
+where
+    E: Error + Send + Sync + 'static,
+    F: FnMut() -> Result<T, E>,
+{
+    retry_loop(
+        &SystemClock,
+        attempts,
+        None,
+        Duration::from_secs(0),
+        op,
+        |_| true,
+    )
+}
+
+/// Like [`retry`], but sleep between attempts, and only retry
+/// [transient](crate::classify::is_transient) errors. Any other error is
+/// returned right away.
+///
+/// The first wait is `base`, and each wait after that is twice as long as
+/// the one before.
+pub fn retry_with_backoff<T, E, F>(attempts: usize, base: Duration, op: F) -> GenResult<T>
+where
+    E: Transient + Error + Send + Sync + 'static,
+    F: FnMut() -> Result<T, E>,
+{
+    retry_with_backoff_on(&SystemClock, attempts, base, op)
+}
+
+/// Like [`retry_with_backoff`], but wait using `clock`.
+pub fn retry_with_backoff_on<T, E, F, C>(
+    clock: &C,
+    attempts: usize,
+    base: Duration,
+    op: F,
+) -> GenResult<T>
+where
+    E: Transient + Error + Send + Sync + 'static,
+    F: FnMut() -> Result<T, E>,
+    C: Clock,
+{
+    retry_loop(clock, attempts, None, base, op, E::is_transient)
+}
+
+/// Like [`retry_with_backoff_on`], but instead of a number of attempts,
+/// keep retrying for as long as less than `budget` has passed since the
+/// first attempt. Once the budget is used up, the last error is returned,
+/// with the context "failed after N attempts".
+///
+/// The waits double from `base` as in [`retry_with_backoff`], but never
+/// run past the end of the budget. `op` always runs at least once.
+pub fn retry_within<T, E, F, C>(clock: &C, budget: Duration, base: Duration, op: F) -> GenResult<T>
+where
+    E: Transient + Error + Send + Sync + 'static,
+    F: FnMut() -> Result<T, E>,
+    C: Clock,
+{
+    retry_loop(clock, usize::MAX, Some(budget), base, op, E::is_transient)
+}
+
+fn retry_loop<T, E, F, R, C>(
+    clock: &C,
+    attempts: usize,
+    budget: Option<Duration>,
+    base: Duration,
+    mut op: F,
+    retryable: R,
+) -> GenResult<T>
+where
+    E: Error + Send + Sync + 'static,
+    F: FnMut() -> Result<T, E>,
+    R: Fn(&E) -> bool,
+    C: Clock,
+{
+    let start = clock.now();
+    let mut delay = base;
+    let mut attempt = 1;
+    loop {
+        let err = match op() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        // Time left in the budget, if there is one.
+        let left = budget.map(|budget| budget.saturating_sub(clock.now() - start));
+        if attempt >= attempts || left == Some(Duration::from_secs(0)) || !retryable(&err) {
+            let plural = if attempt == 1 { "" } else { "s" };
+            return Err(err).context(format!("failed after {} attempt{}", attempt, plural));
+        }
+        clock.sleep(left.map_or(delay, |left| delay.min(left)));
+        delay = delay.saturating_mul(2);
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FakeClock;
+    use crate::format::ChainDisplay;
+    use std::io;
+
+    #[test]
+    fn fails_twice_then_succeeds() {
+        let mut calls = 0;
+        let result = retry(5, || {
+            calls += 1;
+            if calls < 3 {
+                Err(io::Error::other("network outage"))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn final_error_counts_attempts() {
+        let mut calls = 0;
+        let result: GenResult<()> = retry(3, || {
+            calls += 1;
+            Err(io::Error::other("network outage"))
+        });
+        let err = result.unwrap_err();
+        assert_eq!(calls, 3);
+        assert!(err.to_string().contains("3 attempts"));
+        let source = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.to_string(), "network outage");
+    }
+
+    #[test]
+    fn gives_up_with_last_error() {
+        let mut calls = 0;
+        let result: GenResult<()> =
+            retry_with_backoff_on(&FakeClock::new(), 3, Duration::from_millis(1), || {
+                calls += 1;
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("attempt {}", calls),
+                ))
+            });
+        assert_eq!(
+            ChainDisplay(&*result.unwrap_err()).to_string(),
+            "failed after 3 attempts: attempt 3"
+        );
+    }
+
+    #[test]
+    fn fatal_errors_are_not_retried() {
+        let mut calls = 0;
+        let result: GenResult<()> =
+            retry_with_backoff_on(&FakeClock::new(), 3, Duration::from_millis(1), || {
+                calls += 1;
+                Err(io::Error::new(io::ErrorKind::NotFound, "moon not found"))
+            });
+        assert_eq!(result.unwrap_err().to_string(), "failed after 1 attempt");
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn backoff_delays_double() {
+        let clock = FakeClock::new();
+        let start = clock.now();
+        let mut calls = 0;
+        let result: GenResult<()> =
+            retry_with_backoff_on(&clock, 5, Duration::from_millis(10), || {
+                calls += 1;
+                Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))
+            });
+        assert!(result.is_err());
+        assert_eq!(calls, 5);
+        let ms = |n| Duration::from_millis(n);
+        assert_eq!(clock.sleeps(), vec![ms(10), ms(20), ms(40), ms(80)]);
+        assert_eq!(clock.now() - start, ms(150));
+    }
+
+    #[test]
+    fn budget_cuts_off_retries() {
+        let clock = FakeClock::new();
+        let mut calls = 0;
+        let result: GenResult<()> = retry_within(
+            &clock,
+            Duration::from_secs(1),
+            Duration::from_millis(100),
+            || {
+                calls += 1;
+                clock.advance(Duration::from_millis(300));
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("attempt {}", calls),
+                ))
+            },
+        );
+        // Attempts end at 300 and 700 ms, after waits of 100 and 200 ms;
+        // the third ends at 1200 ms, past the budget.
+        assert_eq!(calls, 3);
+        assert_eq!(
+            ChainDisplay(&*result.unwrap_err()).to_string(),
+            "failed after 3 attempts: attempt 3"
+        );
+        let ms = |n| Duration::from_millis(n);
+        assert_eq!(clock.sleeps(), vec![ms(100), ms(200)]);
+    }
+
+    #[test]
+    fn budget_wait_stops_at_deadline() {
+        let clock = FakeClock::new();
+        let start = clock.now();
+        let mut calls = 0;
+        let result: GenResult<()> = retry_within(
+            &clock,
+            Duration::from_secs(1),
+            Duration::from_millis(800),
+            || {
+                calls += 1;
+                Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))
+            },
+        );
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+        let ms = |n| Duration::from_millis(n);
+        assert_eq!(clock.sleeps(), vec![ms(800), ms(200)]);
+        assert_eq!(clock.now() - start, ms(1000));
+    }
+
+    #[test]
+    fn budget_does_not_retry_fatal_errors() {
+        let mut calls = 0;
+        let clock = FakeClock::new();
+        let result: GenResult<()> = retry_within(
+            &clock,
+            Duration::from_secs(1),
+            Duration::from_millis(100),
+            || {
+                calls += 1;
+                Err(io::Error::new(io::ErrorKind::NotFound, "moon not found"))
+            },
+        );
+        assert_eq!(
+            ChainDisplay(&*result.unwrap_err()).to_string(),
+            "failed after 1 attempt: moon not found"
+        );
+        assert_eq!(calls, 1);
+        assert!(clock.sleeps().is_empty());
+    }
+}
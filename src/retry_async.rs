@@ -0,0 +1,76 @@
+// Retrying Futures
+
+// retry() for async code. Nothing here depends on a particular runtime: each
+// attempt is just another future to await. With no runtime to sleep on, there
+// is no backoff between attempts.
+
+use crate::classify::Transient;
+use std::future::Future;
+
+/// Await `op()` up to `attempts` times, returning the first `Ok` or the last
+/// `Err`. Like [`retry_with_backoff`](crate::retry::retry_with_backoff),
+/// errors that aren't [transient](crate::classify::is_transient) are
+/// returned right away.
+///
+/// `op` always runs at least once, even if `attempts` is 0.
+pub async fn retry_async<T, E, Fut, F>(attempts: usize, mut op: F) -> Result<T, E>
+where
+    E: Transient,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt >= attempts || !err.is_transient() => return Err(err),
+            Err(_) => attempt += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    // None of the futures in these tests ever wait on anything, so polling
+    // once is enough.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut cx = Context::from_waker(Waker::noop());
+        match pin!(fut).poll(&mut cx) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future was not ready"),
+        }
+    }
+
+    #[test]
+    fn fails_twice_then_succeeds() {
+        let mut calls = 0;
+        let result = block_on(retry_async(5, || {
+            calls += 1;
+            let calls = calls;
+            async move {
+                if calls < 3 {
+                    Err(io::Error::new(io::ErrorKind::TimedOut, "timed out"))
+                } else {
+                    Ok(calls)
+                }
+            }
+        }));
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn fatal_errors_are_not_retried() {
+        let mut calls = 0;
+        let result: io::Result<()> = block_on(retry_async(5, || {
+            calls += 1;
+            async { Err(io::Error::new(io::ErrorKind::NotFound, "moon not found")) }
+        }));
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,77 @@
+// Errors from main()
+
+// main() can return a Result, but then an error is printed with its Debug
+// form, which is meant for programmers, not users. main_wrapper() prints the
+// whole chain with print_error() instead, and picks the exit code.
+
+use crate::error_print::print_error;
+use crate::prelude::GenResult;
+use std::error::Error;
+use std::process::ExitCode;
+
+/// Run `f`. If it fails, print the error and its causes with
+/// [`print_error`] and return `ExitCode::FAILURE`; otherwise return
+/// `ExitCode::SUCCESS`.
+///
+/// ```no_run
+/// use rust_error_result::{main_wrapper, read_numbers};
+/// use std::io;
+/// use std::process::ExitCode;
+///
+/// fn main() -> ExitCode {
+///     main_wrapper(|| {
+///         let numbers = read_numbers(&mut io::stdin().lock())?;
+///         println!("{}", numbers.iter().sum::<i64>());
+///         Ok(())
+///     })
+/// }
+/// ```
+pub fn main_wrapper<F: FnOnce() -> GenResult<()>>(f: F) -> ExitCode {
+    run_reporting(f, print_error)
+}
+
+fn run_reporting<F, R>(f: F, report: R) -> ExitCode
+where
+    F: FnOnce() -> GenResult<()>,
+    R: FnOnce(&dyn Error),
+{
+    match f() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            report(&*err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error_print::write_error;
+    use crate::ext::ResultExt;
+    use std::io;
+
+    #[test]
+    fn success_exits_cleanly() {
+        let code = run_reporting(|| Ok(()), |_| panic!("nothing to report"));
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert_eq!(main_wrapper(|| Ok(())), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn failure_prints_the_chain() {
+        let mut out = Vec::new();
+        let code = run_reporting(
+            || {
+                let failed: io::Result<()> = Err(io::Error::other("network outage"));
+                failed.context("fetching weather")
+            },
+            |err| write_error(&mut out, err).unwrap(),
+        );
+        assert_eq!(code, ExitCode::FAILURE);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "error: fetching weather\ncaused by: network outage\n"
+        );
+    }
+}
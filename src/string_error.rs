@@ -0,0 +1,124 @@
+// String Errors
+
+// The Debug view of an io::Error made from a message shows a StringError
+// inside it. This is the same idea as a public type: an error that is nothing
+// but a message, with no source. Most messages are string literals, so
+// MsgError holds a Cow, and MsgError::from_static() and err! keep a literal
+// borrowed instead of copying it.
+
+use crate::prelude::GenError;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// An error that is nothing but a message.
+///
+/// ```
+/// use rust_error_result::string_error::MsgError;
+/// use std::borrow::Cow;
+///
+/// let err = MsgError::from_static("moon not found");
+/// assert!(matches!(err.0, Cow::Borrowed(_)));
+/// assert_eq!(err.to_string(), "moon not found");
+///
+/// let name = String::from("moon");
+/// let err = MsgError::from(name.as_str());
+/// assert!(matches!(err.0, Cow::Owned(_)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgError(pub Cow<'static, str>);
+
+/// The old name of [`MsgError`].
+pub type StringError = MsgError;
+
+impl fmt::Display for MsgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for MsgError {}
+
+impl MsgError {
+    /// An error with the message `s`, borrowed rather than copied.
+    pub fn from_static(s: &'static str) -> MsgError {
+        MsgError(Cow::Borrowed(s))
+    }
+}
+
+/// Copies `s`. Use [`MsgError::from_static`] to borrow a `&'static str`.
+impl From<&str> for MsgError {
+    fn from(s: &str) -> MsgError {
+        MsgError(Cow::Owned(s.to_string()))
+    }
+}
+
+impl From<String> for MsgError {
+    fn from(s: String) -> MsgError {
+        MsgError(Cow::Owned(s))
+    }
+}
+
+/// Make a [`GenError`] that is nothing but the message `s`.
+///
+/// `s` is a `&'static str`, which is kept without copying, or an owned
+/// `String`. Pass a borrowed string as `s.to_string()`.
+///
+/// ```
+/// use rust_error_result::string_error::string_error;
+///
+/// let err = string_error("boom");
+/// assert_eq!(err.to_string(), "boom");
+/// assert!(err.source().is_none());
+/// ```
+pub fn string_error<S: Into<Cow<'static, str>>>(s: S) -> GenError {
+    Box::new(MsgError(s.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg_of(err: &GenError) -> &Cow<'static, str> {
+        &err.downcast_ref::<MsgError>().unwrap().0
+    }
+
+    #[test]
+    fn literal_message_is_borrowed() {
+        let err = crate::err!("moon not found");
+        assert!(matches!(msg_of(&err), Cow::Borrowed("moon not found")));
+        assert_eq!(err.to_string(), "moon not found");
+    }
+
+    #[test]
+    fn formatted_message_is_owned() {
+        let ms = 500;
+        let err = crate::err!("timed out after {} ms", ms);
+        assert!(matches!(msg_of(&err), Cow::Owned(_)));
+        assert_eq!(err.to_string(), "timed out after 500 ms");
+
+        let err = crate::err!("timed out after {ms} ms");
+        assert_eq!(err.to_string(), "timed out after 500 ms");
+
+        let err = crate::err!("braces {{}} are escaped");
+        assert_eq!(err.to_string(), "braces {} are escaped");
+    }
+
+    #[test]
+    fn string_error_borrows_static_str() {
+        let err = string_error("moon not found");
+        assert!(matches!(msg_of(&err), Cow::Borrowed("moon not found")));
+
+        let err = string_error(format!("{} not found", "moon"));
+        assert!(matches!(msg_of(&err), Cow::Owned(_)));
+        assert_eq!(err.to_string(), "moon not found");
+    }
+
+    #[test]
+    fn from_any_str_copies() {
+        let name = String::from("moon not found");
+        let err = MsgError::from(name.as_str());
+        drop(name);
+        assert_eq!(err, MsgError(Cow::Owned("moon not found".to_string())));
+    }
+}
@@ -0,0 +1,144 @@
+// Testing Errors
+
+// Most error types, io::Error included, don't implement PartialEq, so tests
+// can't just assert_eq! on them. These helpers compare errors by what they
+// print instead.
+
+use crate::chain::chain;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+/// True if `a` and `b` print the same message, and so does every error in
+/// their source chains.
+pub fn errors_match(a: &dyn Error, b: &dyn Error) -> bool {
+    chain_messages(a) == chain_messages(b)
+}
+
+/// The `Display` output of `err` and each of its sources.
+pub fn chain_messages(err: &dyn Error) -> Vec<String> {
+    chain(err).map(|e| e.to_string()).collect()
+}
+
+/// The value inside `r`, or a panic showing the error's message.
+///
+/// Unlike `assert!(r.is_ok())`, a failure says what went wrong.
+#[track_caller]
+pub fn assert_ok<T: Debug, E: Display>(r: Result<T, E>) -> T {
+    match r {
+        Ok(value) => value,
+        Err(err) => panic!("expected Ok, got error: {}", err),
+    }
+}
+
+/// The error inside `r`, or a panic showing the unexpected value.
+#[track_caller]
+pub fn assert_err<T: Debug, E>(r: Result<T, E>) -> E {
+    match r {
+        Ok(value) => panic!("expected Err, got Ok({:?})", value),
+        Err(err) => err,
+    }
+}
+
+/// Assert that an error's chain has one error for each string given, and
+/// that each error's message contains its string.
+///
+/// The first argument is an error value, not a reference. For a boxed
+/// error such as a `GenError`, pass `*err`.
+///
+/// ```
+/// use rust_error_result::assert_error_chain;
+/// use rust_error_result::ext::ResultExt;
+/// use std::io;
+///
+/// let failed: io::Result<()> = Err(io::Error::other("network outage"));
+/// let err = failed.context("fetching weather").unwrap_err();
+/// assert_error_chain!(*err, ["weather", "outage"]);
+/// ```
+#[macro_export]
+macro_rules! assert_error_chain {
+    ($err:expr, [$($expected:expr),* $(,)?]) => {{
+        let err: &dyn ::std::error::Error = &$err;
+        let actual = $crate::testutil::chain_messages(err);
+        let expected: &[&str] = &[$($expected),*];
+        assert!(
+            actual.len() == expected.len()
+                && actual.iter().zip(expected).all(|(a, e)| a.contains(e)),
+            "error chain {:?} does not match {:?}",
+            actual,
+            expected
+        );
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ContextError;
+    use std::io;
+
+    fn repossessed(root: &str) -> ContextError {
+        ContextError {
+            context: "boat was repossessed".to_string(),
+            source: Box::new(ContextError {
+                context: "failed to transfer $300".to_string(),
+                source: Box::new(io::Error::other(root.to_string())),
+            }),
+        }
+    }
+
+    #[test]
+    fn matching_chains() {
+        assert!(errors_match(
+            &repossessed("network outage"),
+            &repossessed("network outage")
+        ));
+        assert_error_chain!(
+            repossessed("network outage"),
+            ["repossessed", "transfer", "outage"]
+        );
+    }
+
+    #[test]
+    fn mismatching_chains() {
+        assert!(!errors_match(
+            &repossessed("network outage"),
+            &repossessed("power outage")
+        ));
+        let alone = io::Error::other("boat was repossessed");
+        assert!(!errors_match(&repossessed("network outage"), &alone));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match")]
+    fn assert_error_chain_fails_on_wrong_message() {
+        assert_error_chain!(
+            repossessed("network outage"),
+            ["repossessed", "transfer", "power"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match")]
+    fn assert_error_chain_fails_on_wrong_length() {
+        assert_error_chain!(repossessed("network outage"), ["repossessed", "transfer"]);
+    }
+
+    #[test]
+    fn assert_ok_and_err_unwrap() {
+        assert_eq!(assert_ok("7".parse::<i64>()), 7);
+        let err = assert_err("bleen".parse::<i64>());
+        assert_eq!(err.to_string(), "invalid digit found in string");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected Ok, got error: invalid digit found in string")]
+    fn assert_ok_shows_the_error() {
+        assert_ok("bleen".parse::<i64>());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected Err, got Ok(7)")]
+    fn assert_err_shows_the_value() {
+        assert_err("7".parse::<i64>());
+    }
+}
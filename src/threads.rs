@@ -0,0 +1,75 @@
+// Errors Across Threads
+
+// A GenError is Send + Sync, so a thread can hand one back to whoever joins
+// it. spawn_collect() runs a fallible job on its own thread, and join_all()
+// gathers the results.
+
+use crate::catch::panic_message;
+use crate::prelude::GenResult;
+use crate::string_error::MsgError;
+use std::thread::{self, JoinHandle};
+
+/// Run `f` on a new thread. Joining the handle gives back `f`'s result.
+pub fn spawn_collect<T, F>(f: F) -> JoinHandle<GenResult<T>>
+where
+    F: FnOnce() -> GenResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    thread::spawn(f)
+}
+
+/// Wait for every thread in `handles`, and collect their results in order.
+///
+/// If any of them failed, the error from the first in the list is
+/// returned. A thread that panicked counts as failing, with the panic
+/// message as its error. All the threads are joined either way.
+pub fn join_all<T>(handles: Vec<JoinHandle<GenResult<T>>>) -> GenResult<Vec<T>> {
+    let mut values = Vec::with_capacity(handles.len());
+    let mut first_err = None;
+    for handle in handles {
+        let result = handle.join().unwrap_or_else(|payload| {
+            let message = format!("worker thread panicked: {}", panic_message(&*payload));
+            Err(MsgError::from(message).into())
+        });
+        match result {
+            Ok(value) => values.push(value),
+            Err(err) => {
+                first_err.get_or_insert(err);
+            }
+        }
+    }
+    match first_err {
+        Some(err) => Err(err),
+        None => Ok(values),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn all_workers_succeed() {
+        let handles = (1..=3).map(|n| spawn_collect(move || Ok(n * 10))).collect();
+        assert_eq!(join_all(handles).unwrap(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn failing_worker_surfaces_its_error() {
+        let handles = (1..=3)
+            .map(|n| {
+                spawn_collect(move || {
+                    if n == 2 {
+                        Err(io::Error::new(io::ErrorKind::TimedOut, "worker 2 timed out").into())
+                    } else {
+                        Ok(n)
+                    }
+                })
+            })
+            .collect();
+        let err = join_all(handles).unwrap_err();
+        assert_eq!(err.to_string(), "worker 2 timed out");
+        assert!(err.downcast_ref::<io::Error>().is_some());
+    }
+}
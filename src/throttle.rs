@@ -0,0 +1,93 @@
+// Throttling Error Logs
+
+// A retry loop that fails the same way thousands of times will log the same
+// message thousands of times. ErrorThrottle lets each distinct message through
+// at most once per time window, so the log stays readable.
+
+use crate::clock::{Clock, SystemClock};
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+/// Decides which errors are worth logging, allowing each distinct message
+/// at most once per `window`.
+///
+/// Errors are told apart by their `Display` output. The time comes from a
+/// [`Clock`], the real one unless another is given.
+#[derive(Debug, Clone)]
+pub struct ErrorThrottle<C = SystemClock> {
+    window: Duration,
+    seen: HashMap<String, Instant>,
+    clock: C,
+}
+
+impl ErrorThrottle {
+    pub fn new(window: Duration) -> ErrorThrottle {
+        ErrorThrottle::with_clock(window, SystemClock)
+    }
+}
+
+impl<C: Clock> ErrorThrottle<C> {
+    pub fn with_clock(window: Duration, clock: C) -> ErrorThrottle<C> {
+        ErrorThrottle {
+            window,
+            seen: HashMap::new(),
+            clock,
+        }
+    }
+
+    /// True if `err` should be logged now: its message hasn't been allowed
+    /// within the last `window`.
+    pub fn should_log(&mut self, err: &dyn Error) -> bool {
+        let now = self.clock.now();
+        self.should_log_at(err, now)
+    }
+
+    /// Like [`should_log`](ErrorThrottle::should_log), but as if the time
+    /// were `now`.
+    pub fn should_log_at(&mut self, err: &dyn Error, now: Instant) -> bool {
+        let message = err.to_string();
+        match self.seen.get(&message) {
+            Some(&last) if now.saturating_duration_since(last) < self.window => false,
+            _ => {
+                self.seen.insert(message, now);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FakeClock;
+    use std::io;
+
+    #[test]
+    fn once_per_window_per_message() {
+        let mut throttle = ErrorThrottle::new(Duration::from_secs(60));
+        let outage = io::Error::other("network outage");
+        let other = io::Error::other("moon not found");
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert!(throttle.should_log_at(&outage, at(0)));
+        assert!(!throttle.should_log_at(&outage, at(1)));
+        assert!(throttle.should_log_at(&other, at(1)));
+        assert!(!throttle.should_log_at(&outage, at(59)));
+        assert!(throttle.should_log_at(&outage, at(60)));
+        assert!(!throttle.should_log_at(&outage, at(61)));
+    }
+
+    #[test]
+    fn uses_the_given_clock() {
+        let mut throttle = ErrorThrottle::with_clock(Duration::from_secs(60), FakeClock::new());
+        let outage = io::Error::other("network outage");
+
+        assert!(throttle.should_log(&outage));
+        throttle.clock.advance(Duration::from_secs(30));
+        assert!(!throttle.should_log(&outage));
+        throttle.clock.advance(Duration::from_secs(30));
+        assert!(throttle.should_log(&outage));
+    }
+}
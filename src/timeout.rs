@@ -0,0 +1,94 @@
+// Timeouts
+
+// io::Error::new(io::ErrorKind::Other, "timed out") says what went wrong,
+// but not how long we waited, and callers have to compare strings to tell it
+// apart from other errors. TimeoutError is its own type, and knows how long
+// it waited.
+
+use crate::humanize::humanize_duration;
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// An operation didn't finish in time.
+///
+/// A zero wait displays as "operation timed out immediately".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutError {
+    pub waited: Duration,
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // humanize_duration() calls zero "just now", which reads oddly here.
+        if self.waited == Duration::from_secs(0) {
+            return write!(f, "operation timed out immediately");
+        }
+        write!(
+            f,
+            "operation timed out after {}",
+            humanize_duration(self.waited)
+        )
+    }
+}
+
+impl Error for TimeoutError {}
+
+/// Run `f` on a new thread, and wait up to `dur` for it to finish.
+///
+/// There's no way to stop a thread from outside, so if `f` takes too long
+/// it keeps running in the background, and its result is thrown away when
+/// it's done. That's why `f` and its result must be `'static`.
+///
+/// If `f` panics, the panic is passed on to the caller.
+pub fn run_with_timeout<T, F>(dur: Duration, f: F) -> Result<T, TimeoutError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        // The receiver is gone if we already gave up waiting.
+        let _ = sender.send(f());
+    });
+    match receiver.recv_timeout(dur) {
+        Ok(value) => Ok(value),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(TimeoutError { waited: dur }),
+        Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => std::panic::resume_unwind(payload),
+            Ok(()) => unreachable!("the worker sends before it returns"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_closure_succeeds() {
+        let result = run_with_timeout(Duration::from_secs(5), || 6 * 7);
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn slow_closure_times_out() {
+        let result = run_with_timeout(Duration::from_millis(10), || {
+            thread::sleep(Duration::from_millis(500));
+            42
+        });
+        let err = result.unwrap_err();
+        assert_eq!(err.waited, Duration::from_millis(10));
+        assert_eq!(err.to_string(), "operation timed out after 10 milliseconds");
+    }
+
+    #[test]
+    fn zero_wait_times_out_immediately() {
+        let err = TimeoutError {
+            waited: Duration::from_secs(0),
+        };
+        assert_eq!(err.to_string(), "operation timed out immediately");
+    }
+}
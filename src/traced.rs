@@ -0,0 +1,107 @@
+// Stack Traces
+
+// The standard library's error types do not include a stack trace. TracedError
+// wraps any error and grabs one when it's created, much like the error-chain
+// crate does with the backtrace crate.
+
+use crate::prelude::GenError;
+use crate::transparent::Transparent;
+use std::backtrace::Backtrace;
+use std::error::Error;
+use std::fmt;
+
+/// An error along with the stack trace captured when it was created.
+///
+/// The trace is captured with [`Backtrace::capture`], so it is only
+/// collected if the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment
+/// variable asks for it. Like the wrapped error's message, its `source()`
+/// is passed through; see [`Transparent`].
+#[derive(Debug)]
+pub struct TracedError {
+    inner: GenError,
+    backtrace: Backtrace,
+}
+
+impl TracedError {
+    /// Wrap `err`, capturing a backtrace of the current thread.
+    pub fn new<E: Into<GenError>>(err: E) -> TracedError {
+        TracedError::with_backtrace(err.into(), Backtrace::capture())
+    }
+
+    pub(crate) fn with_backtrace(inner: GenError, backtrace: Backtrace) -> TracedError {
+        TracedError { inner, backtrace }
+    }
+
+    /// The stack trace captured by [`TracedError::new`].
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+
+    /// The wrapped error.
+    pub fn inner(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.inner
+    }
+
+    /// Unwrap the error, discarding the backtrace.
+    pub fn into_inner(self) -> GenError {
+        self.inner
+    }
+}
+
+impl fmt::Display for TracedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl Error for TracedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner.source()
+    }
+}
+
+impl Transparent for TracedError {
+    fn wrapped(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ResultExt;
+    use std::backtrace::BacktraceStatus;
+    use std::env;
+    use std::io;
+
+    #[test]
+    fn backtrace_disabled_without_env_var() {
+        if env::var_os("RUST_BACKTRACE").is_some() || env::var_os("RUST_LIB_BACKTRACE").is_some() {
+            return;
+        }
+        let err = TracedError::new(io::Error::other("moon not found"));
+        assert_eq!(err.backtrace().status(), BacktraceStatus::Disabled);
+        assert_eq!(err.to_string(), "moon not found");
+    }
+
+    #[test]
+    fn wrapped_error_stays_reachable() {
+        let err = TracedError::new(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let io_err = crate::downcast_root::<io::Error>(&err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn chain_shows_message_once() {
+        let failed: io::Result<()> = Err(io::Error::other("timed out"));
+        let err = failed.context("fetching weather").unwrap_err();
+        let err = TracedError::new(err);
+        let mut out = Vec::new();
+        crate::write_error(&mut out, &err).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "error: fetching weather\ncaused by: timed out\n"
+        );
+    }
+}
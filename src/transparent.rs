@@ -0,0 +1,116 @@
+// Transparent Wrappers
+
+// Some errors only tag another error with extra data, like a backtrace or an
+// exit code. They display as the wrapped error does, and source() skips over
+// it to its own source, so print_error() doesn't show the same message twice.
+// That hides the wrapped error from source(), though, so a Transparent
+// wrapper says what it wraps, and the helpers that downcast, such as
+// downcast_root() and categorize(), step into it.
+
+use std::any::TypeId;
+use std::error::Error;
+use std::sync::RwLock;
+
+/// An error that displays as another error does, and stands in for it in
+/// the chain.
+///
+/// Its `source()` should be the wrapped error's `source()`, so the message
+/// is printed once. Pass the type to [`register_transparent`] so that
+/// [`downcast_root`](crate::downcast::downcast_root) and the like can still
+/// find the wrapped error.
+pub trait Transparent: Error + 'static {
+    /// The error this one displays as, if there is one.
+    fn wrapped(&self) -> Option<&(dyn Error + 'static)>;
+}
+
+type Unwrap = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a (dyn Error + 'static)>;
+
+// The crate's own transparent wrappers, which don't need registering.
+const BUILTIN: [Unwrap; 6] = [
+    unwrap_as::<crate::apperror::AppError>,
+    unwrap_as::<crate::coded::CodedError>,
+    unwrap_as::<crate::fields::FieldedError>,
+    unwrap_as::<crate::http::HttpError>,
+    unwrap_as::<crate::traced::TracedError>,
+    unwrap_as::<crate::wrap::BoxedError>,
+];
+
+static REGISTERED: RwLock<Vec<(TypeId, Unwrap)>> = RwLock::new(Vec::new());
+
+/// Let chain walkers step into `T`'s wrapped error. Registering a type
+/// more than once has no further effect.
+pub fn register_transparent<T: Transparent>() {
+    let mut registered = REGISTERED
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let type_id = TypeId::of::<T>();
+    if !registered.iter().any(|&(id, _)| id == type_id) {
+        registered.push((type_id, unwrap_as::<T>));
+    }
+}
+
+/// The error that `err` wraps, if it's a known [`Transparent`] wrapper.
+pub(crate) fn wrapped<'a>(err: &'a (dyn Error + 'static)) -> Option<&'a (dyn Error + 'static)> {
+    if let Some(inner) = BUILTIN.iter().find_map(|unwrap| unwrap(err)) {
+        return Some(inner);
+    }
+    let registered = REGISTERED
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    registered.iter().find_map(|(_, unwrap)| unwrap(err))
+}
+
+fn unwrap_as<'a, T: Transparent>(
+    err: &'a (dyn Error + 'static),
+) -> Option<&'a (dyn Error + 'static)> {
+    err.downcast_ref::<T>()?.wrapped()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::chain_contains;
+    use crate::downcast::downcast_root;
+    use std::fmt;
+    use std::io;
+
+    #[derive(Debug)]
+    struct Tagged(io::Error);
+
+    impl fmt::Display for Tagged {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl Error for Tagged {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.0.source()
+        }
+    }
+
+    impl Transparent for Tagged {
+        fn wrapped(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn registered_wrapper_is_stepped_into() {
+        let err = Tagged(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        assert!(err.source().is_none());
+
+        register_transparent::<Tagged>();
+        register_transparent::<Tagged>();
+        let registered = REGISTERED.read().unwrap();
+        let tagged = TypeId::of::<Tagged>();
+        assert_eq!(
+            registered.iter().filter(|&&(id, _)| id == tagged).count(),
+            1
+        );
+        drop(registered);
+        assert!(chain_contains::<io::Error>(&err));
+        let io_err = downcast_root::<io::Error>(&err).unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+    }
+}
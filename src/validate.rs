@@ -0,0 +1,103 @@
+// Validating Input
+
+// When a form has three bad fields, telling the user about only the first
+// one makes for a slow back and forth. Validator checks every field, and
+// reports all the failures together as a MultiError.
+
+use crate::multi::MultiError;
+use crate::prelude::GenError;
+use crate::string_error::MsgError;
+
+/// Collects the failed checks on some input.
+///
+/// ```
+/// use rust_error_result::Validator;
+///
+/// let (name, age) = ("", 200);
+/// let mut v = Validator::new();
+/// v.check(!name.is_empty(), "name is empty");
+/// v.check(age < 150, format!("age {} is too high", age));
+/// let err = v.finish((name, age)).unwrap_err();
+/// assert_eq!(err.causes().len(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<GenError>,
+}
+
+impl Validator {
+    pub fn new() -> Validator {
+        Validator::default()
+    }
+
+    /// Record the error `msg` unless `cond` is true.
+    pub fn check(&mut self, cond: bool, msg: impl Into<String>) {
+        if !cond {
+            self.errors.push(Box::new(MsgError::from(msg.into())));
+        }
+    }
+
+    /// `Ok(value)` if every check passed; otherwise a [`MultiError`] with
+    /// each failure, in the order checked.
+    pub fn finish<T>(self, value: T) -> Result<T, MultiError> {
+        if self.errors.is_empty() {
+            Ok(value)
+        } else {
+            let message = match self.errors.len() {
+                1 => "1 validation error".to_string(),
+                n => format!("{} validation errors", n),
+            };
+            Err(MultiError::new(message, self.errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::LatLng;
+
+    fn validate(name: &str, loc: LatLng) -> Result<(String, LatLng), MultiError> {
+        let mut v = Validator::new();
+        v.check(!name.is_empty(), "name is empty");
+        v.check(
+            loc.lat.abs() <= 90.0,
+            format!("latitude {} is out of range", loc.lat),
+        );
+        v.check(
+            loc.lng.abs() <= 180.0,
+            format!("longitude {} is out of range", loc.lng),
+        );
+        v.finish((name.to_string(), loc))
+    }
+
+    #[test]
+    fn reports_every_failed_check() {
+        let err = validate(
+            "",
+            LatLng {
+                lat: 34.05,
+                lng: 200.0,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2 validation errors\n  \
+             - name is empty\n  \
+             - longitude 200 is out of range"
+        );
+        assert!(err.causes()[0].downcast_ref::<MsgError>().is_some());
+    }
+
+    #[test]
+    fn all_checks_pass() {
+        let home = LatLng {
+            lat: 34.05,
+            lng: -118.24,
+        };
+        let (name, loc) = validate("Los Angeles", home).unwrap();
+        assert_eq!(name, "Los Angeles");
+        assert_eq!(loc, home);
+    }
+}
@@ -0,0 +1,327 @@
+// Result
+
+// Rust doesn't have exceptions. Instead, functions that can fail have a
+// return type that says so. get_weather() returns either Ok(weather), where
+// weather is a new WeatherReport value, or Err(error_value), where
+// error_value is a WeatherError explaining what went wrong.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseFloatError;
+use std::str::FromStr;
+
+/// A point on the globe, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatLng {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+/// Parses `"lat,lng"`, such as `"34.05,-118.24"`.
+///
+/// ```
+/// use rust_error_result::LatLng;
+///
+/// let los_angeles: LatLng = "34.05, -118.24".parse().unwrap();
+/// assert_eq!(los_angeles, LatLng { lat: 34.05, lng: -118.24 });
+/// ```
+impl FromStr for LatLng {
+    type Err = LatLngParseError;
+
+    fn from_str(s: &str) -> Result<LatLng, LatLngParseError> {
+        let (lat, lng) = s
+            .split_once(',')
+            .ok_or_else(|| LatLngParseError::MissingComma(s.to_string()))?;
+        let parse = |part: &str| {
+            let part = part.trim();
+            part.parse::<f64>()
+                .map_err(|source| LatLngParseError::BadNumber {
+                    text: part.to_string(),
+                    source,
+                })
+        };
+        let loc = LatLng {
+            lat: parse(lat)?,
+            lng: parse(lng)?,
+        };
+        if !(loc.lat.abs() <= 90.0 && loc.lng.abs() <= 180.0) {
+            return Err(LatLngParseError::OutOfRange(loc));
+        }
+        Ok(loc)
+    }
+}
+
+/// Why a string couldn't be parsed as a [`LatLng`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LatLngParseError {
+    /// There was no comma between the latitude and longitude.
+    MissingComma(String),
+    /// One of the coordinates wasn't a number.
+    BadNumber {
+        text: String,
+        source: ParseFloatError,
+    },
+    /// The latitude wasn't within ±90°, or the longitude within ±180°.
+    OutOfRange(LatLng),
+}
+
+impl fmt::Display for LatLngParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LatLngParseError::MissingComma(s) => {
+                write!(f, "expected \"lat,lng\", found {:?}", s)
+            }
+            LatLngParseError::BadNumber { text, .. } => {
+                write!(f, "invalid coordinate {:?}", text)
+            }
+            LatLngParseError::OutOfRange(loc) => {
+                write!(f, "no such location: ({}, {})", loc.lat, loc.lng)
+            }
+        }
+    }
+}
+
+impl Error for LatLngParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LatLngParseError::BadNumber { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Weather conditions and the temperature in degrees Fahrenheit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeatherReport {
+    Sunny(i32),
+    Rainy(i32),
+    Snowy(i32),
+}
+
+/// A fairly safe prediction for Southern California.
+pub const THE_USUAL: WeatherReport = WeatherReport::Sunny(72);
+
+/// Why a weather report couldn't be had.
+#[derive(Debug)]
+pub enum WeatherError {
+    /// The weather service couldn't be reached. The `io::Error` is the
+    /// `source()`.
+    Network(io::Error),
+    /// The location isn't on the globe.
+    InvalidLocation(LatLng),
+    /// The weather service is up, but not giving out reports.
+    ServiceUnavailable,
+}
+
+impl fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeatherError::Network(_) => write!(f, "could not reach the weather service"),
+            WeatherError::InvalidLocation(loc) => {
+                write!(f, "no such location: ({}, {})", loc.lat, loc.lng)
+            }
+            WeatherError::ServiceUnavailable => write!(f, "weather service unavailable"),
+        }
+    }
+}
+
+impl Error for WeatherError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WeatherError::Network(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for WeatherError {
+    fn from(err: io::Error) -> WeatherError {
+        WeatherError::Network(err)
+    }
+}
+
+/// Get a weather report for `loc`.
+///
+/// There's no real weather service behind this yet. The report is a
+/// deterministic stub based on latitude: warm and sunny near the equator,
+/// rainy in the middle latitudes, and snowy toward the poles. Coordinates
+/// that aren't on the globe are a [`WeatherError::InvalidLocation`].
+pub fn get_weather(loc: LatLng) -> Result<WeatherReport, WeatherError> {
+    if !(loc.lat.abs() <= 90.0 && loc.lng.abs() <= 180.0) {
+        return Err(WeatherError::InvalidLocation(loc));
+    }
+    let lat = loc.lat.abs();
+    let temp = 90 - lat as i32;
+    Ok(if lat < 30.0 {
+        WeatherReport::Sunny(temp)
+    } else if lat < 60.0 {
+        WeatherReport::Rainy(temp)
+    } else {
+        WeatherReport::Snowy(temp)
+    })
+}
+
+/// A guess at the weather for `loc`, for when we can't get a real report.
+///
+/// The guess depends only on which latitude band `loc` falls in, getting
+/// colder toward the poles, so it is cheap and always the same for the same
+/// place. That makes it a good fallback for `unwrap_or_else`.
+pub fn vague_prediction(loc: LatLng) -> WeatherReport {
+    let lat = loc.lat.abs();
+    if lat < 23.5 {
+        WeatherReport::Sunny(80) // tropics
+    } else if lat < 40.0 {
+        WeatherReport::Sunny(65) // subtropics
+    } else if lat < 66.5 {
+        WeatherReport::Rainy(50) // temperate
+    } else {
+        WeatherReport::Snowy(10) // polar
+    }
+}
+
+/// Fallbacks for a failed weather report.
+///
+/// ```
+/// use rust_error_result::{get_weather, LatLng, THE_USUAL};
+/// use rust_error_result::weather::WeatherResultExt;
+///
+/// let nowhere = LatLng { lat: 91.0, lng: 0.0 };
+/// assert_eq!(get_weather(nowhere).or_usual(), THE_USUAL);
+/// ```
+pub trait WeatherResultExt {
+    /// The report, or [`THE_USUAL`] if there was an error.
+    fn or_usual(self) -> WeatherReport;
+
+    /// The report, or [`vague_prediction`] for `loc` if there was an
+    /// error. The prediction is only made if it's needed.
+    fn or_vague(self, loc: LatLng) -> WeatherReport;
+}
+
+impl<E> WeatherResultExt for Result<WeatherReport, E> {
+    fn or_usual(self) -> WeatherReport {
+        self.unwrap_or(THE_USUAL)
+    }
+
+    fn or_vague(self, loc: LatLng) -> WeatherReport {
+        self.unwrap_or_else(|_err| vague_prediction(loc))
+    }
+}
+
+/// Print a weather report for `loc` to stdout.
+pub fn display_weather(loc: LatLng, report: &WeatherReport) {
+    let (conditions, temp) = match *report {
+        WeatherReport::Sunny(temp) => ("sunny", temp),
+        WeatherReport::Rainy(temp) => ("rainy", temp),
+        WeatherReport::Snowy(temp) => ("snowy", temp),
+    };
+    println!("({}, {}): {}, {}°F", loc.lat, loc.lng, conditions, temp);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stub_report_depends_on_latitude() {
+        let equator = LatLng { lat: 0.0, lng: 0.0 };
+        let los_angeles = LatLng {
+            lat: 34.05,
+            lng: -118.24,
+        };
+        let north_pole = LatLng {
+            lat: 90.0,
+            lng: 0.0,
+        };
+        assert_eq!(get_weather(equator).unwrap(), WeatherReport::Sunny(90));
+        assert_eq!(get_weather(los_angeles).unwrap(), WeatherReport::Rainy(56));
+        assert_eq!(get_weather(north_pole).unwrap(), WeatherReport::Snowy(0));
+    }
+
+    #[test]
+    fn off_the_globe_is_an_error() {
+        let nowhere = LatLng {
+            lat: 91.0,
+            lng: 0.0,
+        };
+        let err = get_weather(nowhere).unwrap_err();
+        assert!(matches!(err, WeatherError::InvalidLocation(loc) if loc == nowhere));
+        assert_eq!(err.to_string(), "no such location: (91, 0)");
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn network_error_is_the_source() {
+        let err = WeatherError::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+        let io_err = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::TimedOut);
+
+        // std's blanket From impl already boxes it into a GenError for `?`.
+        fn forecast() -> crate::prelude::GenResult<WeatherReport> {
+            Ok(get_weather(LatLng {
+                lat: 0.0,
+                lng: 181.0,
+            })?)
+        }
+        let err = forecast().unwrap_err();
+        assert!(err.downcast_ref::<WeatherError>().is_some());
+    }
+
+    #[test]
+    fn vague_prediction_by_latitude_band() {
+        let at = |lat| LatLng { lat, lng: 0.0 };
+        assert_eq!(vague_prediction(at(0.0)), WeatherReport::Sunny(80));
+        assert_eq!(vague_prediction(at(34.05)), WeatherReport::Sunny(65));
+        assert_eq!(vague_prediction(at(-51.5)), WeatherReport::Rainy(50));
+        assert_eq!(vague_prediction(at(90.0)), WeatherReport::Snowy(10));
+        assert_eq!(vague_prediction(at(-90.0)), WeatherReport::Snowy(10));
+    }
+
+    #[test]
+    fn fallbacks_only_apply_on_error() {
+        let hometown = LatLng {
+            lat: 45.0,
+            lng: 0.0,
+        };
+        assert_eq!(get_weather(hometown).or_usual(), WeatherReport::Rainy(45));
+        assert_eq!(
+            get_weather(hometown).or_vague(hometown),
+            WeatherReport::Rainy(45)
+        );
+
+        let failed: io::Result<WeatherReport> = Err(io::Error::other("timed out"));
+        assert_eq!(failed.or_usual(), THE_USUAL);
+
+        let nowhere = LatLng {
+            lat: -91.0,
+            lng: 0.0,
+        };
+        assert_eq!(
+            get_weather(nowhere).or_vague(nowhere),
+            WeatherReport::Snowy(10)
+        );
+    }
+
+    #[test]
+    fn parse_lat_lng() {
+        let los_angeles: LatLng = "34.05,-118.24".parse().unwrap();
+        assert_eq!(
+            los_angeles,
+            LatLng {
+                lat: 34.05,
+                lng: -118.24
+            }
+        );
+
+        let err = "34.05 -118.24".parse::<LatLng>().unwrap_err();
+        assert_eq!(err, LatLngParseError::MissingComma("34.05 -118.24".into()));
+        assert!(err.source().is_none());
+
+        let err = "91,0".parse::<LatLng>().unwrap_err();
+        assert_eq!(err.to_string(), "no such location: (91, 0)");
+
+        let err = "34.05,west".parse::<LatLng>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid coordinate \"west\"");
+        assert!(err.source().unwrap().is::<ParseFloatError>());
+    }
+}
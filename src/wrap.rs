@@ -0,0 +1,165 @@
+// Building Chains
+
+// In the notes, "boat was repossessed" is caused by "failed to transfer $300
+// to United Yacht Supply", which is caused by an io::Error. Wrapped makes a
+// chain like that easy to build in code, one GenError at a time. unnest()
+// goes the other way, removing BoxedError layers that add nothing to the
+// chain.
+
+use crate::chain::MAX_DEPTH;
+use crate::prelude::GenError;
+use crate::transparent::Transparent;
+use std::error::Error;
+use std::fmt;
+
+/// An error with a message, caused by another error.
+///
+/// Displays only the message; the cause is `source()`.
+#[derive(Debug)]
+pub struct Wrapped {
+    message: String,
+    source: GenError,
+}
+
+impl Wrapped {
+    pub fn new<S: Into<String>>(message: S, source: GenError) -> Wrapped {
+        Wrapped {
+            message: message.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for Wrapped {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// A [`Wrapped`] error with the message `msg` and the cause `source`.
+///
+/// ```
+/// use rust_error_result::{chain_len, wrap, GenError};
+/// use std::io;
+///
+/// let err = wrap("calculating tides", GenError::from(io::Error::other("moon not found")));
+/// assert_eq!(chain_len(&*err), 2);
+/// ```
+pub fn wrap<S: Into<String>>(msg: S, source: GenError) -> GenError {
+    Box::new(Wrapped::new(msg, source))
+}
+
+/// A `GenError` that implements `Error`, so it can go where a concrete error
+/// type is needed, such as [`ResultExt`](crate::ext::ResultExt) or another
+/// `GenError`.
+///
+/// Displays as the wrapped error does, and passes its `source()` through;
+/// see [`Transparent`].
+#[derive(Debug)]
+pub struct BoxedError(pub GenError);
+
+impl fmt::Display for BoxedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for BoxedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl Transparent for BoxedError {
+    fn wrapped(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+/// Remove every [`BoxedError`] layer from the outside of `err`, leaving
+/// the `GenError` inside.
+///
+/// Boxing a `GenError` as a `BoxedError` and converting it back, maybe
+/// several times over, adds layers that say nothing new. Anything else is
+/// returned unchanged. At most [`MAX_DEPTH`] layers are removed.
+///
+/// ```
+/// use rust_error_result::wrap::{unnest, BoxedError};
+/// use rust_error_result::GenError;
+/// use std::io;
+///
+/// let inner = GenError::from(io::Error::other("moon not found"));
+/// let err = GenError::from(BoxedError(inner));
+/// assert!(err.downcast_ref::<io::Error>().is_none());
+/// let err = unnest(err);
+/// assert!(err.downcast_ref::<io::Error>().is_some());
+/// ```
+pub fn unnest(mut err: GenError) -> GenError {
+    for _ in 0..MAX_DEPTH {
+        match err.downcast::<BoxedError>() {
+            Ok(boxed) => err = boxed.0,
+            Err(inner) => return inner,
+        }
+    }
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::chain;
+    use crate::downcast::downcast_root;
+    use std::io;
+
+    #[test]
+    fn boat_was_repossessed() {
+        let err = wrap(
+            "boat was repossessed",
+            wrap(
+                "failed to transfer $300 to United Yacht Supply",
+                Box::new(io::Error::other("network outage")),
+            ),
+        );
+
+        let messages: Vec<String> = chain(&*err).map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "boat was repossessed",
+                "failed to transfer $300 to United Yacht Supply",
+                "network outage",
+            ]
+        );
+        assert!(downcast_root::<io::Error>(&*err).is_some());
+    }
+
+    #[test]
+    fn unnest_removes_nested_boxes() {
+        let once = GenError::from(BoxedError(Box::new(io::Error::other("network outage"))));
+        let twice = GenError::from(BoxedError(once));
+        assert_eq!(chain(&*twice).count(), 1);
+        assert!(downcast_root::<io::Error>(&*twice).is_some());
+
+        let err = unnest(twice);
+        let io_err = err.downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_err.to_string(), "network outage");
+        assert_eq!(chain(&*err).count(), 1);
+    }
+
+    #[test]
+    fn unnest_keeps_other_errors() {
+        let err = unnest(Box::new(io::Error::other("network outage")));
+        assert!(err.downcast_ref::<io::Error>().is_some());
+
+        let inner = GenError::from(BoxedError(crate::err!("moon not found")));
+        let err = unnest(wrap("calculating tides", inner));
+        assert_eq!(err.to_string(), "calculating tides");
+        assert!(err.downcast_ref::<Wrapped>().is_some());
+    }
+}
@@ -0,0 +1,43 @@
+// Builds only with the "std" feature off, to check that the chain helpers
+// work against CoreError without std.
+
+#![cfg(not(feature = "std"))]
+#![no_std]
+
+use core::fmt;
+use rust_error_result::core_error::CoreError;
+use rust_error_result::{chain, format_error, root_cause};
+
+#[derive(Debug)]
+struct Outage;
+
+impl fmt::Display for Outage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "network outage")
+    }
+}
+
+impl CoreError for Outage {}
+
+#[derive(Debug)]
+struct Repossessed(Outage);
+
+impl fmt::Display for Repossessed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "boat was repossessed")
+    }
+}
+
+impl CoreError for Repossessed {
+    fn source(&self) -> Option<&dyn CoreError> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn chain_helpers_work_without_std() {
+    let err = Repossessed(Outage);
+    assert_eq!(chain(&err).count(), 2);
+    assert_eq!(format_error(root_cause(&err), false), "network outage");
+    assert_eq!(format_error(&err, false), "boat was repossessed");
+}